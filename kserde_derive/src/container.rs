@@ -0,0 +1,161 @@
+use kserde_derive_impl::{Container, Location};
+use proc_macro::{Delimiter, Span, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+/// Where each part of the input is, so errors can point at it.
+pub struct Spans {
+    name: Span,
+    attributes: Vec<Span>,
+    fields: Vec<Span>,
+    field_attributes: Vec<Vec<Span>>,
+}
+
+impl Spans {
+    pub fn span(&self, location: Location) -> Span {
+        let span = match location {
+            Location::Container => None,
+            Location::ContainerAttribute(i) => self.attributes.get(i).copied(),
+            Location::Field(i) => self.fields.get(i).copied(),
+            Location::FieldAttribute(i, j) => self
+                .field_attributes
+                .get(i)
+                .and_then(|attributes| attributes.get(j))
+                .copied(),
+        };
+        span.unwrap_or(self.name)
+    }
+}
+
+/// Read the leading `#[...]` attributes from `tokens`, returning how many tokens they used.
+fn attributes(tokens: &[TokenTree], contents: &mut Vec<String>, spans: &mut Vec<Span>) -> usize {
+    let mut i = 0;
+    while let (Some(TokenTree::Punct(p)), Some(TokenTree::Group(g))) =
+        (tokens.get(i), tokens.get(i + 1))
+    {
+        if p.as_char() != '#' || g.delimiter() != Delimiter::Bracket {
+            break;
+        }
+        contents.push(g.stream().to_string());
+        spans.push(g.span());
+        i += 2;
+    }
+    i
+}
+
+/// Read each field's attributes and the span of its name, or of its type in a tuple struct.
+fn fields(body: TokenStream, container: &mut Container, spans: &mut Spans) {
+    let tokens: Vec<TokenTree> = body.into_iter().collect();
+    let mut i = 0;
+    while i < tokens.len() {
+        let mut field_attributes = Vec::new();
+        let mut field_attribute_spans = Vec::new();
+        i += attributes(
+            &tokens[i..],
+            &mut field_attributes,
+            &mut field_attribute_spans,
+        );
+
+        // Skip the visibility, like `pub` or `pub(crate)`.
+        if matches!(tokens.get(i), Some(TokenTree::Ident(ident)) if ident.to_string() == "pub") {
+            i += 1;
+            if matches!(tokens.get(i), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis)
+            {
+                i += 1;
+            }
+        }
+        let span = match tokens.get(i) {
+            Some(token) => token.span(),
+            None => break,
+        };
+
+        // The field ends at a `,` that isn't inside the type's generic arguments.
+        let mut depth = 0;
+        let mut previous = None;
+        while let Some(token) = tokens.get(i) {
+            i += 1;
+            if let TokenTree::Punct(p) = token {
+                match p.as_char() {
+                    '<' => depth += 1,
+                    // The `>` of a `->` isn't a closing angle bracket.
+                    '>' if previous != Some('-') => depth -= 1,
+                    ',' if depth == 0 => break,
+                    _ => {}
+                }
+                previous = Some(p.as_char());
+            } else {
+                previous = None;
+            }
+        }
+
+        container.field_attributes.push(field_attributes);
+        spans.field_attributes.push(field_attribute_spans);
+        spans.fields.push(span);
+    }
+}
+
+/// Read the parts of the input that kreflect doesn't expose: the attributes on the type
+/// and its fields, and the type's `where` clause.
+pub fn scan(item: &TokenStream) -> (Container, Spans) {
+    let tokens: Vec<TokenTree> = item.clone().into_iter().collect();
+    let mut container = Container::default();
+    let mut spans = Spans {
+        name: Span::call_site(),
+        attributes: Vec::new(),
+        fields: Vec::new(),
+        field_attributes: Vec::new(),
+    };
+
+    let i = attributes(&tokens, &mut container.attributes, &mut spans.attributes);
+
+    // The name follows the `struct` or `enum` keyword.
+    if let Some(position) = tokens[i..].iter().position(
+        |token| matches!(token, TokenTree::Ident(ident) if ident.to_string() == "struct" || ident.to_string() == "enum"),
+    ) {
+        if let Some(name) = tokens.get(i + position + 1) {
+            spans.name = name.span();
+        }
+    }
+
+    // The fields are in the first brace or parenthesis group outside the generic parameters.
+    // The `where` clause ends at the body of a struct with named fields,
+    // or at the `;` after a tuple struct.
+    let mut where_tokens: Option<Vec<TokenTree>> = None;
+    let mut depth = 0;
+    let mut previous = None;
+    let mut found_fields = false;
+    for token in &tokens[i..] {
+        let punct = match token {
+            TokenTree::Punct(p) => Some(p.as_char()),
+            _ => None,
+        };
+        match (token, &mut where_tokens) {
+            (TokenTree::Punct(p), None) if p.as_char() == '<' => depth += 1,
+            (TokenTree::Punct(p), None) if p.as_char() == '>' && previous != Some('-') => {
+                depth -= 1
+            }
+            (TokenTree::Group(g), None)
+                if depth == 0
+                    && !found_fields
+                    && matches!(g.delimiter(), Delimiter::Brace | Delimiter::Parenthesis) =>
+            {
+                fields(g.stream(), &mut container, &mut spans);
+                found_fields = true;
+            }
+            (TokenTree::Ident(ident), None) if ident.to_string() == "where" => {
+                where_tokens = Some(Vec::new())
+            }
+            (TokenTree::Group(g), Some(_)) if g.delimiter() == Delimiter::Brace => {
+                fields(g.stream(), &mut container, &mut spans);
+                break;
+            }
+            (TokenTree::Punct(p), Some(_)) if p.as_char() == ';' => break,
+            (token, Some(where_tokens)) => where_tokens.push(token.clone()),
+            _ => {}
+        }
+        previous = punct;
+    }
+    if let Some(where_tokens) = where_tokens {
+        container.where_clause = TokenStream::from_iter(where_tokens).to_string();
+    }
+    (container, spans)
+}
@@ -1,16 +1,184 @@
 use kreflect_common::*;
+use kserde_derive_impl::{DeriveError, Location};
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
 
-#[proc_macro_derive(SerializeDeserialize, attributes(skip))]
-pub fn derive_component(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+mod container;
+
+/// Declares the derives, which all accept the same helper attributes.
+macro_rules! derives {
+    ($($(#[$doc: meta])* $name: ident => $function: ident($serialize: expr, $deserialize: expr);)*) => {
+        $(
+            $(#[$doc])*
+            #[proc_macro_derive(
+                $name,
+                attributes(
+                    skip,
+                    skip_serializing,
+                    skip_deserializing,
+                    default,
+                    bound,
+                    serialize_bound,
+                    deserialize_bound,
+                    serialize_with,
+                    deserialize_with,
+                    with,
+                    from,
+                    into,
+                    alias,
+                    skip_serializing_if
+                )
+            )]
+            pub fn $function(item: TokenStream) -> TokenStream {
+                derive(item, $serialize, $deserialize)
+            }
+        )*
+    };
+}
+
+derives! {
+    /// Derives both `kserde::Serialize` and `kserde::Deserialize`.
+    /// This is the same as deriving [Serialize] and [Deserialize] separately.
+    ///
+    /// Fields whose types mention a type parameter get bounds like
+    /// `Vec<T>: kserde::Serialize<KSer>` automatically. The type's own `where` clause is kept.
+    /// To replace the inferred bounds use `#[bound = "..."]` on the type, or
+    /// `#[serialize_bound = "..."]` and `#[deserialize_bound = "..."]` to set each impl's
+    /// bounds separately. The generated impls name the serializer `KSer`, the deserializer
+    /// `KDes` and the deserializer's lifetime `'kserde`.
+    ///
+    /// A field whose type doesn't implement kserde's traits can name functions to use instead:
+    /// `#[serialize_with = "path"]` for a `fn<S: kserde::Serializer>(&T, &mut S)`,
+    /// `#[deserialize_with = "path"]` for a `fn<'a, D: kserde::Deserializer<'a>>(&mut D) -> Option<T>`,
+    /// or `#[with = "module"]` for a module's `serialize` and `deserialize` functions.
+    /// `#[into = "Type"]` on the type serializes it by converting a clone of it to `Type`,
+    /// and `#[from = "Type"]` deserializes a `Type` and converts it.
+    ///
+    /// `#[alias = "old_name"]` lets a field also be read from another property name and can be
+    /// repeated. `#[skip_serializing_if = "path"]` leaves a field out when a `fn(&T) -> bool`
+    /// returns `true`, like `Option::is_none` or `Vec::is_empty`.
+    ///
+    /// `#[skip]` leaves a field out of both directions. `#[skip_serializing]` never writes it
+    /// and `#[skip_deserializing]` never reads it. A field that isn't read gets `Default::default()`,
+    /// or the result of calling the function named by `#[default = "path"]`. That function
+    /// also provides the value when the field's property is missing.
+    SerializeDeserialize => derive_component(true, true);
+
+    /// Derives `kserde::Serialize`. Accepts the same attributes as [SerializeDeserialize].
+    Serialize => derive_serialize(true, false);
+
+    /// Derives `kserde::Deserialize`. Accepts the same attributes as [SerializeDeserialize].
+    Deserialize => derive_deserialize(false, true);
+}
+
+fn derive(
+    item: proc_macro::TokenStream,
+    serialize: bool,
+    deserialize: bool,
+) -> proc_macro::TokenStream {
+    expand(item, serialize, deserialize)
+        .unwrap_or_else(|(message, span)| compile_error(&message, span))
+}
+
+fn expand(
+    item: TokenStream,
+    serialize: bool,
+    deserialize: bool,
+) -> Result<TokenStream, (String, Span)> {
+    let (container, spans) = container::scan(&item);
     let mut rust_tokens = Vec::new();
     token_stream_to_rust_tokens(item, &mut rust_tokens);
 
-    println!("TOKENS: {:#?}", rust_tokens);
     let mut parser = Parser::new(&rust_tokens);
-    let parse_result = parser.parse().expect("Could not parse");
+    let parse_result = parser.parse().into_iter().next().ok_or_else(|| {
+        (
+            "kserde couldn't parse this type".to_string(),
+            Span::call_site(),
+        )
+    })?;
+    let error = |error: DeriveError| (error.message, spans.span(error.location));
     let mut output_string = String::new();
-    output_string += &kserde_derive_impl::kserde_serialize_impl(&parse_result);
-    output_string += &kserde_derive_impl::kserde_deserialize_impl(&parse_result);
-    println!("OUTPUT STRING: {}", output_string);
-    output_string.parse().unwrap()
+    if serialize {
+        output_string +=
+            &kserde_derive_impl::kserde_serialize_impl(&parse_result, &container).map_err(error)?;
+    }
+    if deserialize {
+        output_string += &kserde_derive_impl::kserde_deserialize_impl(&parse_result, &container)
+            .map_err(error)?;
+    }
+    let output: TokenStream = output_string.parse().map_err(|_| {
+        (
+            "kserde generated code that doesn't parse. This is a bug in kserde.".to_string(),
+            Span::call_site(),
+        )
+    })?;
+    Ok(field_spans(output, &spans))
+}
+
+/// Replace each field marker, like `__kserde_field_0 { ... }`, with the code inside it
+/// spanned to that field.
+fn field_spans(tokens: TokenStream, spans: &container::Spans) -> TokenStream {
+    let mut output = Vec::new();
+    let mut tokens = tokens.into_iter();
+    while let Some(token) = tokens.next() {
+        let field = match &token {
+            TokenTree::Ident(ident) => ident
+                .to_string()
+                .strip_prefix(kserde_derive_impl::FIELD_MARKER)
+                .and_then(|index| index.parse().ok()),
+            _ => None,
+        };
+        match (field, token) {
+            (Some(field), _) => {
+                if let Some(TokenTree::Group(group)) = tokens.next() {
+                    output.extend(set_spans(
+                        group.stream(),
+                        spans.span(Location::Field(field)),
+                    ));
+                }
+            }
+            (None, TokenTree::Group(group)) => {
+                let mut new_group =
+                    Group::new(group.delimiter(), field_spans(group.stream(), spans));
+                new_group.set_span(group.span());
+                output.push(new_group.into());
+            }
+            (None, token) => output.push(token),
+        }
+    }
+    output.into_iter().collect()
+}
+
+fn set_spans(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| {
+            let mut token = match token {
+                TokenTree::Group(group) => {
+                    Group::new(group.delimiter(), set_spans(group.stream(), span)).into()
+                }
+                token => token,
+            };
+            token.set_span(span);
+            token
+        })
+        .collect()
+}
+
+/// `compile_error!("message");` reported at `span`.
+fn compile_error(message: &str, span: Span) -> TokenStream {
+    let mut literal = Literal::string(message);
+    literal.set_span(span);
+    let tokens: Vec<TokenTree> = vec![
+        Ident::new("compile_error", span).into(),
+        Punct::new('!', Spacing::Alone).into(),
+        Group::new(Delimiter::Parenthesis, TokenTree::from(literal).into()).into(),
+        Punct::new(';', Spacing::Alone).into(),
+    ];
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            token.set_span(span);
+            token
+        })
+        .collect()
 }
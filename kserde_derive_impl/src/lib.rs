@@ -2,67 +2,383 @@ use std::borrow::Cow;
 
 use kreflect_common::*;
 
-fn serialize_fields(properties: &mut String, fields: &Vec<Field>) {
-    for (i, field) in fields.iter().enumerate() {
-        let skip = field_contains_attribute(field, "skip");
-        if !skip {
-            if let Some(name) = field.name.as_ref() {
-                *properties += &format!("    serializer.property(\"{}\", &self.{});\n", name, name);
-            } else {
-                *properties += &format!("    serializer.property(\"{}\", &self.{});\n", i, i);
+/// Parts of the input that kreflect doesn't expose, read from the derive's tokens.
+#[derive(Default)]
+pub struct Container {
+    /// The type's `where` clause predicates, without the `where` keyword.
+    pub where_clause: String,
+    /// The contents of each `#[...]` attribute on the type, like `bound = "T: Clone"`.
+    pub attributes: Vec<String>,
+    /// The contents of each field's `#[...]` attributes, in field order.
+    pub field_attributes: Vec<Vec<String>>,
+}
+
+impl Container {
+    /// The value of a `name = "value"` attribute on the type.
+    fn attribute(&self, name: &str) -> Option<String> {
+        find_attribute(&self.attributes, name).flatten()
+    }
+
+    /// Check if the field at `index` has an attribute, like `#[skip]`.
+    fn field_has_attribute(&self, index: usize, name: &str) -> bool {
+        self.field_attributes
+            .get(index)
+            .is_some_and(|attributes| find_attribute(attributes, name).is_some())
+    }
+
+    /// The value of a `name = "value"` attribute on the field at `index`.
+    fn field_attribute(&self, index: usize, name: &str) -> Option<String> {
+        self.field_attributes
+            .get(index)
+            .and_then(|attributes| find_attribute(attributes, name).flatten())
+    }
+
+    /// Check if the field at `index` is left out when serializing.
+    fn skip_serializing(&self, index: usize) -> bool {
+        self.field_has_attribute(index, "skip")
+            || self.field_has_attribute(index, "skip_serializing")
+    }
+
+    /// Check if the field at `index` is never read when deserializing.
+    fn skip_deserializing(&self, index: usize) -> bool {
+        self.field_has_attribute(index, "skip")
+            || self.field_has_attribute(index, "skip_deserializing")
+    }
+
+    /// The expression for the value of the field at `index` when it isn't deserialized.
+    fn default_value(&self, index: usize) -> String {
+        match self.field_attribute(index, "default") {
+            Some(function) => format!("{}()", function),
+            None => "std::default::Default::default()".to_string(),
+        }
+    }
+
+    /// The values of every `name = "value"` attribute on the field at `index`,
+    /// for attributes that can be repeated.
+    fn field_attribute_values(&self, index: usize, name: &str) -> Vec<String> {
+        self.field_attributes
+            .get(index)
+            .map_or(&[][..], |attributes| &attributes[..])
+            .iter()
+            .filter_map(|a| parse_attribute(a))
+            .filter(|(n, _)| n == name)
+            .filter_map(|(_, value)| value)
+            .collect()
+    }
+
+    /// The function that serializes the field at `index`, if it doesn't use its own impl.
+    fn serialize_with(&self, index: usize) -> Option<String> {
+        self.field_attribute(index, "serialize_with")
+            .or_else(|| Some(self.field_attribute(index, "with")? + "::serialize"))
+    }
+
+    /// The function that deserializes the field at `index`, if it doesn't use its own impl.
+    fn deserialize_with(&self, index: usize) -> Option<String> {
+        self.field_attribute(index, "deserialize_with")
+            .or_else(|| Some(self.field_attribute(index, "with")? + "::deserialize"))
+    }
+
+    /// Check that each of the derive's own attributes is in a place it's allowed and has
+    /// the right form. Attributes that belong to other macros are ignored.
+    fn validate(&self) -> Result<(), DeriveError> {
+        for (i, attribute) in self.attributes.iter().enumerate() {
+            validate_attribute(attribute, false)
+                .map_err(|message| DeriveError::new(message, Location::ContainerAttribute(i)))?;
+        }
+        for (i, attributes) in self.field_attributes.iter().enumerate() {
+            for (j, attribute) in attributes.iter().enumerate() {
+                validate_attribute(attribute, true)
+                    .map_err(|message| DeriveError::new(message, Location::FieldAttribute(i, j)))?;
             }
         }
+        Ok(())
     }
 }
 
-pub fn kserde_serialize_impl(value: &Value) -> String {
-    match value {
-        Value::Struct(_struct) => {
-            let (generic_lifetimes, mut generic_types, generic_consts) =
-                _struct.generic_parameters.lifetimes_types_consts();
+/// Where in the input a [DeriveError] should be reported.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Location {
+    /// The type itself.
+    Container,
+    /// The type's attribute at this index of [Container::attributes].
+    ContainerAttribute(usize),
+    /// The field at this index.
+    Field(usize),
+    /// A field's attribute, indexed like [Container::field_attributes].
+    FieldAttribute(usize, usize),
+}
 
-            generic_types += "KSer: kserde::Serializer, ";
+/// Starts the marker that wraps each field's generated code, like `__kserde_field_0 { ... }`.
+/// The derive replaces the marker with the code spanned to [Location::Field], so errors in
+/// it, like a field type that doesn't implement `Serialize`, point at the field.
+pub const FIELD_MARKER: &str = "__kserde_field_";
 
-            let generic_args = _struct.generic_parameters.as_args();
+fn at_field(index: usize, code: &str) -> String {
+    format!("{}{} {{ {} }}", FIELD_MARKER, index, code)
+}
 
-            let mut properties = String::new();
-            match &_struct.fields {
-                Fields::Struct(fields) => {
-                    serialize_fields(&mut properties, fields);
-                }
-                Fields::Tuple(fields) => {
-                    serialize_fields(&mut properties, fields);
-                }
-                Fields::Unit => todo!(),
+/// A problem with the derive's input, reported as a `compile_error!` at its location.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeriveError {
+    pub message: String,
+    pub location: Location,
+}
+
+impl DeriveError {
+    fn new(message: impl Into<String>, location: Location) -> Self {
+        Self {
+            message: message.into(),
+            location,
+        }
+    }
+}
+
+/// The derive's attributes, whether each goes on fields or on the type, and whether it
+/// takes a `= "value"`.
+const ATTRIBUTES: &[(&str, bool, bool)] = &[
+    ("skip", true, false),
+    ("skip_serializing", true, false),
+    ("skip_deserializing", true, false),
+    ("default", true, true),
+    ("bound", false, true),
+    ("serialize_bound", false, true),
+    ("deserialize_bound", false, true),
+    ("serialize_with", true, true),
+    ("deserialize_with", true, true),
+    ("with", true, true),
+    ("from", false, true),
+    ("into", false, true),
+    ("alias", true, true),
+    ("skip_serializing_if", true, true),
+];
+
+fn validate_attribute(attribute: &str, on_field: bool) -> Result<(), String> {
+    let name = attribute
+        .split(|c: char| c == '=' || c == '(' || c.is_whitespace())
+        .next()
+        .unwrap_or("");
+    let (name, field_attribute, has_value) = match ATTRIBUTES.iter().find(|(n, ..)| *n == name) {
+        Some(&kind) => kind,
+        None => return Ok(()),
+    };
+    if field_attribute != on_field {
+        return Err(if on_field {
+            format!("`{}` can only be used on the type, not on a field", name)
+        } else {
+            format!("`{}` can only be used on a field", name)
+        });
+    }
+    match parse_attribute(attribute) {
+        Some((_, Some(_))) if has_value => Ok(()),
+        Some((_, None)) if !has_value && !attribute.contains('(') => Ok(()),
+        _ if has_value => Err(format!("expected `#[{} = \"...\"]`", name)),
+        _ => Err(format!("expected `#[{}]` without a value", name)),
+    }
+}
+
+/// Find an attribute by name. Gives its value, which is `None` if it doesn't have one.
+fn find_attribute(attributes: &[String], name: &str) -> Option<Option<String>> {
+    attributes
+        .iter()
+        .filter_map(|a| parse_attribute(a))
+        .find(|(n, _)| n == name)
+        .map(|(_, value)| value)
+}
+
+/// Split an attribute's contents into its name and its string value, if it has one.
+/// `skip` gives `("skip", None)` and `alias = "old"` gives `("alias", Some("old"))`.
+fn parse_attribute(attribute: &str) -> Option<(String, Option<String>)> {
+    let (name, value) = match attribute.find('=') {
+        Some(i) => (&attribute[..i], Some(attribute[i + 1..].trim())),
+        None => (attribute, None),
+    };
+    let value = match value {
+        Some(value) => Some(parse_string_literal(value)?),
+        None => None,
+    };
+    Some((name.trim().to_string(), value))
+}
+
+fn parse_string_literal(literal: &str) -> Option<String> {
+    let inner = literal.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next()? {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                c => value.push(c),
             }
+        } else {
+            value.push(c);
+        }
+    }
+    Some(value)
+}
+
+/// The names of the type's generic parameters that aren't lifetimes.
+fn type_parameters(generic_parameters: &GenericParams) -> Vec<String> {
+    generic_parameters
+        .as_args()
+        .split(',')
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty() && !p.starts_with('\''))
+        .collect()
+}
 
+/// Check if a type mentions any of the given generic parameters.
+fn mentions_parameter(_type: &str, parameters: &[String]) -> bool {
+    _type
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .any(|word| parameters.iter().any(|p| p == word))
+}
+
+/// Build a `where` clause from the type's own predicates followed by the derive's bounds.
+///
+/// `bound` overrides the inferred bounds when the type has a bound attribute.
+fn where_clause(container: &Container, bound: Option<String>, inferred: String) -> String {
+    let mut predicates = container.where_clause.trim().to_string();
+    if !predicates.is_empty() && !predicates.ends_with(',') {
+        predicates += ",";
+    }
+    match bound {
+        Some(bound) => predicates += &bound,
+        None => predicates += &inferred,
+    }
+    if predicates.trim().is_empty() {
+        String::new()
+    } else {
+        format!("where {}", predicates)
+    }
+}
+
+/// The fields of a struct. A unit struct has none.
+fn struct_fields<'a, 'b>(_struct: &'b Struct<'a>) -> &'b [Field<'a>] {
+    match &_struct.fields {
+        Fields::Struct(fields) | Fields::Tuple(fields) => fields,
+        Fields::Unit => &[],
+    }
+}
+
+/// Check the input before generating an impl for it.
+fn validate<'a, 'b>(
+    value: &'b Value<'a>,
+    container: &Container,
+) -> Result<&'b Struct<'a>, DeriveError> {
+    container.validate()?;
+    match value {
+        Value::Struct(_struct) => Ok(_struct),
+        Value::Enum(_) => Err(DeriveError::new(
+            "kserde can't derive for enums yet",
+            Location::Container,
+        )),
+    }
+}
+
+fn serialize_fields(properties: &mut String, fields: &[Field], container: &Container) {
+    for (i, field) in fields.iter().enumerate() {
+        if !container.skip_serializing(i) {
+            let name: Cow<str> = match field.name.as_ref() {
+                Some(name) => name.clone(),
+                None => i.to_string().into(),
+            };
+            let value = match container.serialize_with(i) {
+                Some(function) => format!("&kserde::SerializeWith(&self.{}, {})", name, function),
+                None => format!("&self.{}", name),
+            };
+            let property = format!("serializer.property(\"{}\", {});", name, value);
+            let code = match container.field_attribute(i, "skip_serializing_if") {
+                Some(predicate) => format!("if !{}(&self.{}) {{ {} }}", predicate, name, property),
+                None => property,
+            };
+            *properties += &format!("    {}\n", at_field(i, &code));
+        }
+    }
+}
+
+/// Bounds requiring each field type that mentions a type parameter to implement `trait_bound`.
+/// Only fields for which `uses_impl` is `true` need the impl.
+fn inferred_bounds(
+    _struct: &Struct,
+    trait_bound: &str,
+    uses_impl: impl Fn(usize) -> bool,
+) -> String {
+    let parameters = type_parameters(&_struct.generic_parameters);
+    let mut bounds = String::new();
+    for (i, field) in struct_fields(_struct).iter().enumerate() {
+        let _type = field._type.as_string();
+        if uses_impl(i) && mentions_parameter(&_type, &parameters) {
+            bounds += &format!(" {}: {},", _type, trait_bound);
+        }
+    }
+    bounds
+}
+
+pub fn kserde_serialize_impl(value: &Value, container: &Container) -> Result<String, DeriveError> {
+    let _struct = validate(value, container)?;
+    let (generic_lifetimes, mut generic_types, generic_consts) =
+        _struct.generic_parameters.lifetimes_types_consts();
+
+    // With `into` the type is converted to a proxy type that's serialized instead.
+    let (inferred, body) = match container.attribute("into") {
+        Some(into) => (
+            format!(" {}: kserde::Serialize<KSer>,", into),
             format!(
-                r#"impl<{}{}{}> kserde::Serialize<KSer> for {}<{}> {{
+                "        let value: {} = std::clone::Clone::clone(self).into();\n        kserde::Serialize::serialize(&value, serializer);",
+                into
+            ),
+        ),
+        None => {
+            let mut properties = String::new();
+            serialize_fields(&mut properties, struct_fields(_struct), container);
+            (
+                inferred_bounds(_struct, "kserde::Serialize<KSer>", |i| {
+                    !container.skip_serializing(i) && container.serialize_with(i).is_none()
+                }),
+                format!(
+                    "        serializer.begin_object();\n{}\n        serializer.end_object();",
+                    properties
+                ),
+            )
+        }
+    };
+
+    let where_clause = where_clause(
+        container,
+        container
+            .attribute("serialize_bound")
+            .or_else(|| container.attribute("bound")),
+        inferred,
+    );
+
+    generic_types += "KSer: kserde::Serializer, ";
+
+    let generic_args = _struct.generic_parameters.as_args();
+
+    Ok(format!(
+        r#"impl<{}{}{}> kserde::Serialize<KSer> for {}<{}> {} {{
     fn serialize(&self, serializer: &mut KSer) {{
-        serializer.begin_object();
 {}
-        serializer.end_object();
     }}
 }}"#,
-                generic_lifetimes,
-                generic_types,
-                &generic_consts,
-                _struct.name,
-                generic_args,
-                properties
-            )
-        }
-        Value::Enum(_) => {
-            todo!()
-        }
-    }
+        generic_lifetimes,
+        generic_types,
+        &generic_consts,
+        _struct.name,
+        generic_args,
+        where_clause,
+        body
+    ))
 }
 
 pub fn deserialize_fields(
     properties_declaration: &mut String,
     deserialize_match: &mut String,
     property_assignment: &mut String,
-    fields: &Vec<Field>,
+    in_place_match: &mut String,
+    fields: &[Field],
+    container: &Container,
 ) {
     for (i, field) in fields.iter().enumerate() {
         let name: Cow<str> = if let Some(field_name) = field.name.as_ref() {
@@ -71,103 +387,184 @@ pub fn deserialize_fields(
             i.to_string().into()
         };
         let _type = field._type.as_string();
+        // The property's name and any aliases it can also be read from, like `"x" | "old_x"`.
+        let pattern = std::iter::once(name.to_string())
+            .chain(container.field_attribute_values(i, "alias"))
+            .map(|name| format!("{:?}", name))
+            .collect::<Vec<_>>()
+            .join(" | ");
 
-        let skip = field_contains_attribute(field, "skip");
-
-        if !skip {
-            if _type.get(0..6).map_or(false, |s| s == "Option") {
-                *properties_declaration += &format!("    let mut f_{}: {} = None;\n", name, _type);
-                *deserialize_match += &format!(
-                    "                \"{}\" => f_{} = Some(<{}>::deserialize(deserializer)?),\n",
-                    name,
-                    name,
-                    &_type[7.._type.len() - 1]
-                );
-                *property_assignment += &format!("    {}: f_{},\n", name, name);
+        if !container.skip_deserializing(i) {
+            *properties_declaration += &format!(
+                "    {}\n",
+                at_field(i, &format!("let mut f_{}: Option<{}> = None;", name, _type))
+            );
+            // A missing property is an error unless there's a `default` or the type has a value
+            // for it, like `None`. A type deserialized with a function may not implement
+            // `Deserialize`, so it can't provide one.
+            let missing = if container.field_has_attribute(i, "default") {
+                container.default_value(i)
+            } else if container.deserialize_with(i).is_some() {
+                "return None".to_string()
             } else {
-                *properties_declaration +=
-                    &format!("    let mut f_{}: Option<{}> = None;\n", name, _type);
-                *deserialize_match += &format!(
-                    "                \"{}\" => f_{} = Some(<{}>::deserialize(deserializer)?),\n",
-                    name, name, _type
+                format!(
+                    "<{} as kserde::Deserialize<'kserde, KDes>>::missing_field()?",
+                    _type
+                )
+            };
+            let assignment = format!(
+                "{}: match f_{} {{ Some(v) => v, None => {} }},",
+                name, name, missing
+            );
+            *property_assignment += &format!("        {}\n", at_field(i, &assignment));
+            let (read, read_in_place) = match container.deserialize_with(i) {
+                Some(function) => (
+                    format!("f_{} = Some({}(deserializer)?)", name, function),
+                    format!("self.{} = {}(deserializer)?", name, function),
+                ),
+                None => (
+                    format!("f_{} = Some(<{}>::deserialize(deserializer)?)", name, _type),
+                    format!(
+                        "kserde::Deserialize::deserialize_in_place(&mut self.{}, deserializer)?",
+                        name
+                    ),
+                ),
+            };
+            // Repeated properties, including through an alias, follow the deserializer's
+            // `DuplicateKeys` policy.
+            let arm = |read: &str| {
+                let arm = format!(
+                    "{} => if seen_keys.check(deserializer, \"{}\")? {{ {} }} else {{ kserde::skip_value(deserializer)? }},",
+                    pattern, name, read
                 );
-                *property_assignment += &format!("        {}: f_{}?,\n", name, name);
-            }
+                format!("                {}\n", at_field(i, &arm))
+            };
+            *deserialize_match += &arm(&read);
+            *in_place_match += &arm(&read_in_place);
         } else {
             // Assign a default value to the property if it's skipped.
-            *property_assignment +=
-                &format!("        {}: std::default::Default::default(),\n", name);
+            let assignment = format!("{}: {},", name, container.default_value(i));
+            *property_assignment += &format!("        {}\n", at_field(i, &assignment));
         }
     }
 }
 
-pub fn kserde_deserialize_impl(value: &Value) -> String {
-    match value {
-        Value::Struct(_struct) => {
-            let (mut generic_lifetimes, mut generic_types, generic_consts) =
-                _struct.generic_parameters.lifetimes_types_consts();
+/// Bounds that make the deserializer's `'kserde` lifetime outlive each of the type's lifetimes,
+/// so that borrowed fields like `&'a str` can be deserialized.
+fn kserde_outlives(generic_lifetimes: &str) -> String {
+    let mut bounds = String::new();
+    for lifetime in generic_lifetimes.split(',') {
+        let name = lifetime.split(':').next().unwrap_or("").trim();
+        if !name.is_empty() {
+            bounds += &format!("'kserde: {}, ", name);
+        }
+    }
+    bounds
+}
 
-            generic_lifetimes += "'kserde, ";
-            generic_types += "KDes: kserde::Deserializer<'kserde>, ";
+pub fn kserde_deserialize_impl(
+    value: &Value,
+    container: &Container,
+) -> Result<String, DeriveError> {
+    let _struct = validate(value, container)?;
+    let (mut generic_lifetimes, mut generic_types, generic_consts) =
+        _struct.generic_parameters.lifetimes_types_consts();
 
-            let generic_args = _struct.generic_parameters.as_args();
+    let from = container.attribute("from");
+    let inferred = match &from {
+        Some(from) => format!(" {}: kserde::Deserialize<'kserde, KDes>,", from),
+        None => inferred_bounds(_struct, "kserde::Deserialize<'kserde, KDes>", |i| {
+            !container.skip_deserializing(i) && container.deserialize_with(i).is_none()
+        }),
+    };
+    let where_clause = where_clause(
+        container,
+        container
+            .attribute("deserialize_bound")
+            .or_else(|| container.attribute("bound"))
+            .map(|bound| kserde_outlives(&generic_lifetimes) + &bound),
+        kserde_outlives(&generic_lifetimes) + &inferred,
+    );
 
-            let mut deserialize_match = String::new();
-            let mut properties_declaration = String::new();
-            let mut property_assignment = String::new();
+    generic_lifetimes += "'kserde, ";
+    generic_types += "KDes: kserde::Deserializer<'kserde>, ";
 
-            match &_struct.fields {
-                Fields::Struct(fields) => deserialize_fields(
-                    &mut properties_declaration,
-                    &mut deserialize_match,
-                    &mut property_assignment,
-                    fields,
-                ),
-                Fields::Tuple(fields) => deserialize_fields(
-                    &mut properties_declaration,
-                    &mut deserialize_match,
-                    &mut property_assignment,
-                    fields,
-                ),
-                Fields::Unit => todo!(),
-            }
-            format!(
-                r#"impl<{}{}{}> kserde::Deserialize<'kserde, KDes> for {}<{}> {{
+    let generic_args = _struct.generic_parameters.as_args();
+
+    // With `from` a proxy type is deserialized and then converted to the type.
+    if let Some(from) = from {
+        return Ok(format!(
+            r#"impl<{}{}{}> kserde::Deserialize<'kserde, KDes> for {}<{}> {} {{
+    fn deserialize(deserializer: &mut KDes) -> Option<Self> {{
+        <{} as kserde::Deserialize<'kserde, KDes>>::deserialize(deserializer).map(std::convert::From::from)
+    }}
+}}"#,
+            generic_lifetimes,
+            generic_types,
+            &generic_consts,
+            _struct.name,
+            generic_args,
+            where_clause,
+            from
+        ));
+    }
+
+    let mut deserialize_match = String::new();
+    let mut properties_declaration = String::new();
+    let mut property_assignment = String::new();
+    let mut in_place_match = String::new();
+
+    deserialize_fields(
+        &mut properties_declaration,
+        &mut deserialize_match,
+        &mut property_assignment,
+        &mut in_place_match,
+        struct_fields(_struct),
+        container,
+    );
+    let seen_keys = if deserialize_match.is_empty() {
+        ""
+    } else {
+        "        let mut seen_keys = kserde::SeenKeys::new(deserializer);\n"
+    };
+    Ok(format!(
+        r#"impl<{}{}{}> kserde::Deserialize<'kserde, KDes> for {}<{}> {} {{
     fn deserialize(deserializer: &mut KDes) -> Option<Self> {{
         deserializer.begin_object().then(|| {{}})?;
-{}
+{}{}
         while let Some(p) = deserializer.has_property() {{
             match &*p {{
-{}              _ => {{}}
+{}              _ => kserde::skip_value(deserializer)?,
             }}
         }}
         Some(Self {{
 {}
         }})
     }}
-}}"#,
-                generic_lifetimes,
-                generic_types,
-                &generic_consts,
-                _struct.name,
-                generic_args,
-                properties_declaration,
-                deserialize_match,
-                property_assignment
-            )
-        }
-        Value::Enum(_) => {
-            todo!()
-        }
-    }
-}
 
-/// Check if a [Field] contains an attribute name.
-fn field_contains_attribute(field: &Field, attribute: &str) -> bool {
-    field
-        .attributes
-        .iter()
-        .any(|a| a.path.segments.iter().any(|s| s.name == attribute))
+    fn deserialize_in_place(&mut self, deserializer: &mut KDes) -> Option<()> {{
+        deserializer.begin_object().then(|| {{}})?;
+{}        while let Some(p) = deserializer.has_property() {{
+            match &*p {{
+{}              _ => kserde::skip_value(deserializer)?,
+            }}
+        }}
+        Some(())
+    }}
+}}"#,
+        generic_lifetimes,
+        generic_types,
+        &generic_consts,
+        _struct.name,
+        generic_args,
+        where_clause,
+        seen_keys,
+        properties_declaration,
+        deserialize_match,
+        property_assignment,
+        seen_keys,
+        in_place_match
+    ))
 }
 
 #[test]
@@ -184,5 +581,51 @@ fn kersde_impl() {
         }]),
     });
 
-    println!("{}", kserde_deserialize_impl(&value));
+    assert!(kserde_deserialize_impl(&value, &Container::default()).is_ok());
+
+    // Each field's code is marked so the derive can point errors in it at the field.
+    let serialize = kserde_serialize_impl(&value, &Container::default()).unwrap();
+    assert!(serialize.contains("__kserde_field_0 { serializer.property(\"x\", &self.x); }"));
+}
+
+#[test]
+fn attribute_errors() {
+    let value = Value::Struct(Struct {
+        name: "Thing".into(),
+        visibility: Visibility::Private,
+        generic_parameters: GenericParams(Vec::new()),
+        fields: Fields::Tuple(Vec::new()),
+    });
+    let error = |attributes: &[&str], field_attributes: &[&str]| {
+        let container = Container {
+            attributes: attributes.iter().map(|a| a.to_string()).collect(),
+            field_attributes: vec![field_attributes.iter().map(|a| a.to_string()).collect()],
+            ..Default::default()
+        };
+        kserde_serialize_impl(&value, &container).err()
+    };
+
+    assert_eq!(
+        error(
+            &["derive(Debug)", "bound = \"T: Clone\""],
+            &["skip", "doc = \"x\""]
+        ),
+        None
+    );
+    assert_eq!(
+        error(&["bound"], &[]).map(|e| e.location),
+        Some(Location::ContainerAttribute(0))
+    );
+    assert_eq!(
+        error(&[], &["doc = \"x\"", "skip = \"yes\""]).map(|e| e.location),
+        Some(Location::FieldAttribute(0, 1))
+    );
+    assert_eq!(
+        error(&[], &["bound = \"T: Clone\""]).map(|e| e.message),
+        Some("`bound` can only be used on the type, not on a field".to_string())
+    );
+    assert_eq!(
+        error(&["skip"], &[]).map(|e| e.message),
+        Some("`skip` can only be used on a field".to_string())
+    );
 }
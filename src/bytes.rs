@@ -0,0 +1,46 @@
+use crate::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::ops::{Deref, DerefMut};
+
+/// Serializes its contents as a single blob of bytes instead of an array of numbers.
+///
+/// JSON stores bytes as an encoded string, see [crate::BytesEncoding].
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytes<T = Vec<u8>>(pub T);
+
+impl<T> Deref for Bytes<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Bytes<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Bytes<T> {
+    fn from(t: T) -> Self {
+        Bytes(t)
+    }
+}
+
+impl<S: Serializer, T: AsRef<[u8]>> Serialize<S> for Bytes<T> {
+    fn serialize(&self, serializer: &mut S) {
+        serializer.bytes(self.0.as_ref())
+    }
+}
+
+impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for Bytes<Vec<u8>> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        deserializer.bytes().map(|b| Bytes(b.into_owned()))
+    }
+}
+
+impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for Bytes<Cow<'a, [u8]>> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        deserializer.bytes().map(Bytes)
+    }
+}
@@ -1,4 +1,21 @@
+use crate::{DeserializeKey, MapKey};
 use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::convert::{TryFrom, TryInto};
+use std::ffi::OsString;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+};
+use std::ops::{Range, RangeInclusive};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// If a value returns `None` then it should be assumed that the deserializer is
 /// no longer in a valid state.
@@ -7,8 +24,35 @@ pub trait Deserializer<'a> {
     fn string(&mut self) -> Option<Cow<'a, str>>;
     fn bool(&mut self) -> Option<bool>;
     fn i64(&mut self) -> Option<i64>;
+    /// Defaults to reading an `i64` and converting it.
+    fn u64(&mut self) -> Option<u64> {
+        self.i64().and_then(|i| i.try_into().ok())
+    }
+    /// Defaults to reading an `i64` and converting it.
+    fn i128(&mut self) -> Option<i128> {
+        self.i64().map(Into::into)
+    }
+    /// Defaults to reading an `i64` and converting it.
+    fn u128(&mut self) -> Option<u128> {
+        self.i64().and_then(|i| i.try_into().ok())
+    }
     fn f64(&mut self) -> Option<f64>;
+    /// Defaults to reading an array of numbers.
+    fn bytes(&mut self) -> Option<Cow<'a, [u8]>> {
+        self.begin_array().then_some(())?;
+        let mut bytes = Vec::new();
+        while self.has_array_value() {
+            bytes.push(u8::try_from(self.u64()?).ok()?);
+        }
+        Some(Cow::Owned(bytes))
+    }
     fn any<'b>(&'b mut self) -> Option<AnyValue<'a>>;
+    /// If the next value is `null` consume it and return `true`, otherwise leave it.
+    /// This is how `Option` tells `None` apart from a value.
+    /// By default nothing is treated as `null`.
+    fn null(&mut self) -> bool {
+        false
+    }
 
     // I'd prefer the rest of this to be a different trait that
     // borrows from the deserializer, but I couldn't figure out
@@ -18,47 +62,221 @@ pub trait Deserializer<'a> {
     /// When this returns `None` we're at the end of the object or an error was encountered.
     /// The name of the property is returned.
     fn has_property(&mut self) -> Option<Cow<'a, str>>;
+    /// Like [has_property] but for map entries whose keys may not be strings.
+    /// By default keys are strings.
+    fn has_key(&mut self) -> Option<MapKey<'a>> {
+        self.has_property().map(MapKey::String)
+    }
 
     fn begin_array(&mut self) -> bool;
     /// When this returns `None` we're at the end of the array or an error was encountered.
     fn has_array_value(&mut self) -> bool;
     fn get_context_mut(&mut self) -> &mut Self::Context;
+
+    /// Record why deserialization failed, for failures that aren't caused by malformed input.
+    /// Deserializers that don't track errors can ignore this.
+    fn set_error(&mut self, _error: DeserializeError) {}
+    /// The error recorded with [set_error], if any.
+    fn error(&self) -> Option<&DeserializeError> {
+        None
+    }
+
+    /// How objects that repeat a key should be handled.
+    fn duplicate_keys(&self) -> DuplicateKeys {
+        DuplicateKeys::KeepLast
+    }
+
+    /// Where the next value starts in the input, like a byte offset, for error messages.
+    fn position(&mut self) -> Option<usize> {
+        None
+    }
+}
+
+/// What to do when an object contains the same key more than once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateKeys {
+    /// Fail to deserialize.
+    Error,
+    /// Ignore later values for a key.
+    KeepFirst,
+    /// Later values replace earlier values.
+    #[default]
+    KeepLast,
+}
+
+/// Applies a deserializer's [DuplicateKeys] policy to the keys of one object.
+///
+/// Call [SeenKeys::check] after reading each key and before reading its value.
+pub struct SeenKeys {
+    duplicate_keys: DuplicateKeys,
+    positions: HashMap<String, Option<usize>>,
+}
+
+impl SeenKeys {
+    pub fn new<'a, D: Deserializer<'a>>(deserializer: &D) -> Self {
+        Self {
+            duplicate_keys: deserializer.duplicate_keys(),
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Returns `Some(true)` if the key's value should be used and `Some(false)` if it should
+    /// be skipped with [skip_value]. If duplicates are an error this sets a
+    /// [DeserializeError::DuplicateKey] and returns `None`.
+    pub fn check<'a, D: Deserializer<'a>>(
+        &mut self,
+        deserializer: &mut D,
+        key: &str,
+    ) -> Option<bool> {
+        if self.duplicate_keys == DuplicateKeys::KeepLast {
+            return Some(true);
+        }
+        let position = deserializer.position();
+        match self.positions.get(key) {
+            None => {
+                self.positions.insert(key.to_string(), position);
+                Some(true)
+            }
+            Some(_) if self.duplicate_keys == DuplicateKeys::KeepFirst => Some(false),
+            Some(&first) => {
+                deserializer.set_error(DeserializeError::DuplicateKey {
+                    key: key.to_string(),
+                    first,
+                    second: position,
+                });
+                None
+            }
+        }
+    }
+}
+
+/// Reads and discards the next value.
+pub fn skip_value<'a, D: Deserializer<'a>>(deserializer: &mut D) -> Option<()> {
+    crate::Thing::deserialize(deserializer).map(|_| ())
+}
+
+/// Explains why a value failed to deserialize when the input itself was well formed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeserializeError {
+    /// A `&str` or `&[u8]` was requested but the value couldn't be borrowed from the source,
+    /// for example because a JSON string contained escape sequences.
+    CannotBorrow,
+    /// An object repeated a key and the deserializer's [DuplicateKeys] policy is `Error`.
+    /// The positions are where each of the key's values start, if the deserializer knows.
+    DuplicateKey {
+        key: String,
+        first: Option<usize>,
+        second: Option<usize>,
+    },
+}
+
+impl std::fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeserializeError::CannotBorrow => write!(
+                f,
+                "value can't be borrowed from the source, deserialize to an owned type instead"
+            ),
+            DeserializeError::DuplicateKey {
+                key,
+                first: Some(first),
+                second: Some(second),
+            } => write!(
+                f,
+                "duplicate key `{}`, first at {} and again at {}",
+                key, first, second
+            ),
+            DeserializeError::DuplicateKey { key, .. } => write!(f, "duplicate key `{}`", key),
+        }
+    }
 }
 
+impl std::error::Error for DeserializeError {}
+
 pub trait Deserialize<'a, D: Deserializer<'a>>: Sized {
     fn deserialize(deserializer: &mut D) -> Option<Self>;
+
+    /// Deserialize into an existing value instead of constructing a new one.
+    ///
+    /// Implementations reuse allocations where they can. Objects only overwrite
+    /// the properties that are present, which allows partial updates.
+    /// If this returns `None` then `self` may be partially overwritten.
+    fn deserialize_in_place(&mut self, deserializer: &mut D) -> Option<()> {
+        *self = Self::deserialize(deserializer)?;
+        Some(())
+    }
+
+    /// The value to use when an object is missing the property for a struct field.
+    /// By default a missing field fails to deserialize.
+    fn missing_field() -> Option<Self> {
+        None
+    }
 }
 
 impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for String {
     fn deserialize(deserializer: &mut D) -> Option<Self> {
         deserializer.string().map(|s| s.to_string())
     }
+
+    fn deserialize_in_place(&mut self, deserializer: &mut D) -> Option<()> {
+        let s = deserializer.string()?;
+        self.clear();
+        self.push_str(&s);
+        Some(())
+    }
 }
 
-impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for Cow<'a, str> {
+impl<'a: 'b, 'b, D: Deserializer<'a>> Deserialize<'a, D> for Cow<'b, str> {
     fn deserialize(deserializer: &mut D) -> Option<Self> {
         deserializer.string()
     }
 }
 
-impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for i32 {
+/// Fails with [DeserializeError::CannotBorrow] if the string isn't stored as-is in the source.
+impl<'a: 'b, 'b, D: Deserializer<'a>> Deserialize<'a, D> for &'b str {
     fn deserialize(deserializer: &mut D) -> Option<Self> {
-        deserializer.i64().map(|v| v as i32)
+        match deserializer.string()? {
+            Cow::Borrowed(s) => Some(s),
+            Cow::Owned(_) => {
+                deserializer.set_error(DeserializeError::CannotBorrow);
+                None
+            }
+        }
     }
 }
 
-impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for i64 {
+/// Fails with [DeserializeError::CannotBorrow] if the bytes aren't stored as-is in the source.
+/// Formats that encode bytes, like JSON, can never borrow them.
+impl<'a: 'b, 'b, D: Deserializer<'a>> Deserialize<'a, D> for &'b [u8] {
     fn deserialize(deserializer: &mut D) -> Option<Self> {
-        deserializer.i64()
+        match deserializer.bytes()? {
+            Cow::Borrowed(b) => Some(b),
+            Cow::Owned(_) => {
+                deserializer.set_error(DeserializeError::CannotBorrow);
+                None
+            }
+        }
     }
 }
 
-impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for usize {
-    fn deserialize(deserializer: &mut D) -> Option<Self> {
-        deserializer.i64().map(|i| i as usize)
-    }
+/// Out of range values fail to deserialize instead of wrapping.
+macro_rules! deserialize_integer {
+    ($($t:ty => $method:ident),*) => {
+        $(
+            impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for $t {
+                fn deserialize(deserializer: &mut D) -> Option<Self> {
+                    <$t>::try_from(deserializer.$method()?).ok()
+                }
+            }
+        )*
+    };
 }
 
+deserialize_integer!(
+    i8 => i64, i16 => i64, i32 => i64, i64 => i64, isize => i64, i128 => i128,
+    u8 => u64, u16 => u64, u32 => u64, u64 => u64, usize => u64, u128 => u128
+);
+
 impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for f32 {
     fn deserialize(deserializer: &mut D) -> Option<Self> {
         deserializer.f64().map(|f| f as f32)
@@ -86,22 +304,176 @@ impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>> Deserialize<'a, D> for Vec<
         }
         Some(vec)
     }
+
+    /// Existing elements are deserialized in place and extra elements are removed.
+    fn deserialize_in_place(&mut self, deserializer: &mut D) -> Option<()> {
+        deserializer.begin_array().then_some(())?;
+        let mut len = 0;
+        while deserializer.has_array_value() {
+            if let Some(t) = self.get_mut(len) {
+                t.deserialize_in_place(deserializer)?;
+            } else {
+                self.push(T::deserialize(deserializer)?);
+            }
+            len += 1;
+        }
+        self.truncate(len);
+        Some(())
+    }
 }
 
-impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>> Deserialize<'a, D>
-    for std::collections::HashMap<String, T>
+impl<
+        'a,
+        D: Deserializer<'a>,
+        K: DeserializeKey<'a> + Eq + Hash,
+        T: Deserialize<'a, D>,
+        HASHER: BuildHasher + Default,
+    > Deserialize<'a, D> for HashMap<K, T, HASHER>
 {
     fn deserialize(deserializer: &mut D) -> Option<Self> {
-        let mut hash_map = std::collections::HashMap::new();
+        let mut hash_map = HashMap::default();
         deserializer.begin_object().then(|| {})?;
-        while let Some(key) = deserializer.has_property() {
+        let mut seen_keys = SeenKeys::new(deserializer);
+        while let Some(key) = deserializer.has_key() {
+            if !seen_keys.check(deserializer, &key.to_str())? {
+                skip_value(deserializer)?;
+                continue;
+            }
+            let key = K::from_key(key)?;
             let t = T::deserialize(deserializer)?;
-            hash_map.insert(key.to_string(), t);
+            hash_map.insert(key, t);
         }
         Some(hash_map)
     }
 }
 
+impl<'a, D: Deserializer<'a>, K: DeserializeKey<'a> + Ord, T: Deserialize<'a, D>> Deserialize<'a, D>
+    for BTreeMap<K, T>
+{
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        let mut b_tree_map = BTreeMap::new();
+        deserializer.begin_object().then_some(())?;
+        let mut seen_keys = SeenKeys::new(deserializer);
+        while let Some(key) = deserializer.has_key() {
+            if !seen_keys.check(deserializer, &key.to_str())? {
+                skip_value(deserializer)?;
+                continue;
+            }
+            let key = K::from_key(key)?;
+            let t = T::deserialize(deserializer)?;
+            b_tree_map.insert(key, t);
+        }
+        Some(b_tree_map)
+    }
+}
+
+/// Deserializes each value of an array and passes it to `push`.
+fn deserialize_sequence<'a, D: Deserializer<'a>, T: Deserialize<'a, D>>(
+    deserializer: &mut D,
+    mut push: impl FnMut(T),
+) -> Option<()> {
+    deserializer.begin_array().then_some(())?;
+    while deserializer.has_array_value() {
+        push(T::deserialize(deserializer)?)
+    }
+    Some(())
+}
+
+impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D> + Eq + Hash, HASHER: BuildHasher + Default>
+    Deserialize<'a, D> for HashSet<T, HASHER>
+{
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        let mut hash_set = HashSet::default();
+        deserialize_sequence(deserializer, |t| {
+            hash_set.insert(t);
+        })?;
+        Some(hash_set)
+    }
+}
+
+impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D> + Ord> Deserialize<'a, D> for BTreeSet<T> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        let mut b_tree_set = BTreeSet::new();
+        deserialize_sequence(deserializer, |t| {
+            b_tree_set.insert(t);
+        })?;
+        Some(b_tree_set)
+    }
+}
+
+impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>> Deserialize<'a, D> for VecDeque<T> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        let mut vec_deque = VecDeque::new();
+        deserialize_sequence(deserializer, |t| vec_deque.push_back(t))?;
+        Some(vec_deque)
+    }
+}
+
+impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>> Deserialize<'a, D> for LinkedList<T> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        let mut linked_list = LinkedList::new();
+        deserialize_sequence(deserializer, |t| linked_list.push_back(t))?;
+        Some(linked_list)
+    }
+}
+
+impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D> + Ord> Deserialize<'a, D> for BinaryHeap<T> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        let mut binary_heap = BinaryHeap::new();
+        deserialize_sequence(deserializer, |t| binary_heap.push(t))?;
+        Some(binary_heap)
+    }
+}
+
+impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>> Deserialize<'a, D> for Box<T> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        T::deserialize(deserializer).map(Box::new)
+    }
+
+    fn deserialize_in_place(&mut self, deserializer: &mut D) -> Option<()> {
+        (**self).deserialize_in_place(deserializer)
+    }
+}
+
+impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>> Deserialize<'a, D> for Rc<T> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        T::deserialize(deserializer).map(Rc::new)
+    }
+}
+
+impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>> Deserialize<'a, D> for Arc<T> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        T::deserialize(deserializer).map(Arc::new)
+    }
+}
+
+impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>> Deserialize<'a, D> for Cell<T> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        T::deserialize(deserializer).map(Cell::new)
+    }
+}
+
+impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>> Deserialize<'a, D> for RefCell<T> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        T::deserialize(deserializer).map(RefCell::new)
+    }
+}
+
+impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>> Deserialize<'a, D> for Mutex<T> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        T::deserialize(deserializer).map(Mutex::new)
+    }
+}
+
+/// Slices are always deserialized as [Cow::Owned].
+impl<'a, 'b, D: Deserializer<'a>, T: Deserialize<'a, D> + Clone> Deserialize<'a, D>
+    for Cow<'b, [T]>
+{
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        Vec::deserialize(deserializer).map(Cow::Owned)
+    }
+}
+
 impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>, const COUNT: usize> Deserialize<'a, D>
     for [T; COUNT]
 {
@@ -139,11 +511,256 @@ impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>, const COUNT: usize> Deseria
             }
         }
     }
+
+    fn deserialize_in_place(&mut self, deserializer: &mut D) -> Option<()> {
+        deserializer.begin_array().then_some(())?;
+        for t in self.iter_mut() {
+            deserializer.has_array_value().then_some(())?;
+            t.deserialize_in_place(deserializer)?;
+        }
+
+        // This is needed to consume the end of the array.
+        if !deserializer.has_array_value() {
+            Some(())
+        } else {
+            None
+        }
+    }
+}
+
+/// `null` deserializes to `None`, and so does a missing struct field.
+impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>> Deserialize<'a, D> for Option<T> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        if deserializer.null() {
+            Some(None)
+        } else {
+            T::deserialize(deserializer).map(Some)
+        }
+    }
+
+    fn deserialize_in_place(&mut self, deserializer: &mut D) -> Option<()> {
+        if deserializer.null() {
+            *self = None;
+            return Some(());
+        }
+        match self {
+            Some(value) => value.deserialize_in_place(deserializer),
+            None => {
+                *self = Some(T::deserialize(deserializer)?);
+                Some(())
+            }
+        }
+    }
+
+    fn missing_field() -> Option<Self> {
+        Some(None)
+    }
 }
 
 impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for () {
-    fn deserialize(_deserializer: &mut D) -> Option<Self> {
-        Some(())
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        match deserializer.any()? {
+            AnyValue::Null => Some(()),
+            _ => None,
+        }
+    }
+}
+
+/// Tuples are deserialized from arrays with exactly as many values as the tuple.
+macro_rules! deserialize_tuple {
+    ($($name:ident),*) => {
+        impl<'a, D: Deserializer<'a>, $($name: Deserialize<'a, D>),*> Deserialize<'a, D>
+            for ($($name,)*)
+        {
+            fn deserialize(deserializer: &mut D) -> Option<Self> {
+                deserializer.begin_array().then_some(())?;
+                let tuple = ($(
+                    {
+                        deserializer.has_array_value().then_some(())?;
+                        <$name>::deserialize(deserializer)?
+                    },
+                )*);
+
+                // This is needed to consume the end of the array.
+                if !deserializer.has_array_value() {
+                    Some(tuple)
+                } else {
+                    None
+                }
+            }
+        }
+    };
+}
+
+deserialize_tuple!(T0);
+deserialize_tuple!(T0, T1);
+deserialize_tuple!(T0, T1, T2);
+deserialize_tuple!(T0, T1, T2, T3);
+deserialize_tuple!(T0, T1, T2, T3, T4);
+deserialize_tuple!(T0, T1, T2, T3, T4, T5);
+deserialize_tuple!(T0, T1, T2, T3, T4, T5, T6);
+deserialize_tuple!(T0, T1, T2, T3, T4, T5, T6, T7);
+deserialize_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8);
+deserialize_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9);
+deserialize_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+deserialize_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+
+impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for char {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        let s = deserializer.string()?;
+        let mut chars = s.chars();
+        let c = chars.next()?;
+        if chars.next().is_none() {
+            Some(c)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for Duration {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        deserializer.begin_object().then_some(())?;
+
+        let mut secs = None;
+        let mut nanos = None;
+        while let Some(p) = deserializer.has_property() {
+            match &*p {
+                "secs" => secs = Some(u64::deserialize(deserializer)?),
+                "nanos" => nanos = Some(u32::deserialize(deserializer)?),
+                _ => skip_value(deserializer)?,
+            }
+        }
+
+        let nanos = nanos?;
+        if nanos >= 1_000_000_000 {
+            return None;
+        }
+        Some(Duration::new(secs?, nanos))
+    }
+}
+
+impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for SystemTime {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        deserializer.begin_object().then_some(())?;
+
+        let mut secs = None;
+        let mut nanos = None;
+        while let Some(p) = deserializer.has_property() {
+            match &*p {
+                "secs_since_epoch" => secs = Some(i64::deserialize(deserializer)?),
+                "nanos_since_epoch" => nanos = Some(u32::deserialize(deserializer)?),
+                _ => skip_value(deserializer)?,
+            }
+        }
+
+        let (secs, nanos) = (secs?, nanos?);
+        if nanos >= 1_000_000_000 {
+            return None;
+        }
+        let time = if secs >= 0 {
+            UNIX_EPOCH.checked_add(Duration::from_secs(secs as u64))?
+        } else {
+            UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))?
+        };
+        time.checked_add(Duration::from_nanos(nanos as u64))
+    }
+}
+
+impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for PathBuf {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        deserializer.string().map(|s| PathBuf::from(&*s))
+    }
+}
+
+impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for OsString {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        deserializer.string().map(|s| OsString::from(&*s))
+    }
+}
+
+/// Deserializes types from strings using their `FromStr` implementation.
+macro_rules! deserialize_from_str {
+    ($($t:ty),*) => {
+        $(
+            impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for $t {
+                fn deserialize(deserializer: &mut D) -> Option<Self> {
+                    deserializer.string()?.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+deserialize_from_str!(
+    IpAddr,
+    Ipv4Addr,
+    Ipv6Addr,
+    SocketAddr,
+    SocketAddrV4,
+    SocketAddrV6
+);
+
+/// Zero fails to deserialize.
+macro_rules! deserialize_non_zero {
+    ($($t:ty => $inner:ty),*) => {
+        $(
+            impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for $t {
+                fn deserialize(deserializer: &mut D) -> Option<Self> {
+                    <$t>::new(<$inner>::deserialize(deserializer)?)
+                }
+            }
+        )*
+    };
+}
+
+deserialize_non_zero!(
+    NonZeroI8 => i8, NonZeroI16 => i16, NonZeroI32 => i32, NonZeroI64 => i64,
+    NonZeroI128 => i128, NonZeroIsize => isize, NonZeroU8 => u8, NonZeroU16 => u16,
+    NonZeroU32 => u32, NonZeroU64 => u64, NonZeroU128 => u128, NonZeroUsize => usize
+);
+
+impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>> Deserialize<'a, D> for Wrapping<T> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        T::deserialize(deserializer).map(Wrapping)
+    }
+}
+
+/// Deserializes the `start` and `end` properties shared by the range types.
+fn deserialize_range_bounds<'a, D: Deserializer<'a>, T: Deserialize<'a, D>>(
+    deserializer: &mut D,
+) -> Option<(T, T)> {
+    deserializer.begin_object().then_some(())?;
+
+    let mut start = None;
+    let mut end = None;
+    while let Some(p) = deserializer.has_property() {
+        match &*p {
+            "start" => start = Some(T::deserialize(deserializer)?),
+            "end" => end = Some(T::deserialize(deserializer)?),
+            _ => skip_value(deserializer)?,
+        }
+    }
+    Some((start?, end?))
+}
+
+impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>> Deserialize<'a, D> for Range<T> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        let (start, end) = deserialize_range_bounds(deserializer)?;
+        Some(start..end)
+    }
+}
+
+impl<'a, D: Deserializer<'a>, T: Deserialize<'a, D>> Deserialize<'a, D> for RangeInclusive<T> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        let (start, end) = deserialize_range_bounds(deserializer)?;
+        Some(start..=end)
+    }
+}
+
+impl<'a, D: Deserializer<'a>, T: ?Sized> Deserialize<'a, D> for PhantomData<T> {
+    fn deserialize(deserializer: &mut D) -> Option<Self> {
+        <()>::deserialize(deserializer).map(|_| PhantomData)
     }
 }
 
@@ -152,7 +769,12 @@ impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for () {
 pub enum AnyValue<'a> {
     String(std::borrow::Cow<'a, str>),
     Bool(bool),
+    /// An integer that was represented exactly in the source.
+    Integer(i128),
+    /// An integer too large for `Integer`.
+    UInteger(u128),
     Number(f64),
+    Bytes(Cow<'a, [u8]>),
     Object,
     Array,
     Null,
@@ -166,9 +788,27 @@ impl<'a> AnyValue<'a> {
         }
     }
 
+    /// Get this value as an `f64`. Integers are converted and may lose precision.
     pub fn number(self) -> Option<f64> {
         match self {
             Self::Number(v) => Some(v),
+            Self::Integer(v) => Some(v as f64),
+            Self::UInteger(v) => Some(v as f64),
+            _ => None,
+        }
+    }
+
+    /// Get this value as an `i64` if it's an integer in range.
+    pub fn integer(self) -> Option<i64> {
+        match self {
+            Self::Integer(v) => i64::try_from(v).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn bytes(self) -> Option<Cow<'a, [u8]>> {
+        match self {
+            Self::Bytes(v) => Some(v),
             _ => None,
         }
     }
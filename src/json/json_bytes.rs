@@ -0,0 +1,96 @@
+/// How byte arrays are encoded as JSON strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BytesEncoding {
+    /// Standard base64 with padding, as described in RFC 4648.
+    #[default]
+    Base64,
+    /// URL and filename safe base64 without padding.
+    Base64Url,
+    /// Lowercase hexadecimal, two characters per byte.
+    Hex,
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const HEX_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+
+impl BytesEncoding {
+    pub(crate) fn encode(self, bytes: &[u8], s: &mut String) {
+        match self {
+            BytesEncoding::Base64 => encode_base64(bytes, BASE64_ALPHABET, true, s),
+            BytesEncoding::Base64Url => encode_base64(bytes, BASE64_URL_ALPHABET, false, s),
+            BytesEncoding::Hex => {
+                for byte in bytes {
+                    s.push(HEX_ALPHABET[(byte >> 4) as usize] as char);
+                    s.push(HEX_ALPHABET[(byte & 0xF) as usize] as char);
+                }
+            }
+        }
+    }
+
+    /// Padding is accepted but not required for either base64 variant.
+    pub(crate) fn decode(self, s: &str) -> Option<Vec<u8>> {
+        match self {
+            BytesEncoding::Base64 => decode_base64(s, BASE64_ALPHABET),
+            BytesEncoding::Base64Url => decode_base64(s, BASE64_URL_ALPHABET),
+            BytesEncoding::Hex => {
+                let s = s.as_bytes();
+                if s.len() % 2 == 1 {
+                    return None;
+                }
+                s.chunks(2)
+                    .map(|pair| {
+                        let high = (pair[0] as char).to_digit(16)?;
+                        let low = (pair[1] as char).to_digit(16)?;
+                        Some((high << 4 | low) as u8)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+fn encode_base64(bytes: &[u8], alphabet: &[u8; 64], pad: bool, s: &mut String) {
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        // Each 3 byte chunk becomes 4 characters, a partial chunk becomes fewer.
+        let characters = chunk.len() + 1;
+        for i in 0..characters {
+            s.push(alphabet[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+        }
+        if pad {
+            for _ in characters..4 {
+                s.push('=');
+            }
+        }
+    }
+}
+
+fn decode_base64(s: &str, alphabet: &[u8; 64]) -> Option<Vec<u8>> {
+    let s = s.trim_end_matches('=').as_bytes();
+    // A single leftover character can't encode a whole byte.
+    if s.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(s.len() * 3 / 4);
+    for chunk in s.chunks(4) {
+        let mut n = 0;
+        for (i, c) in chunk.iter().enumerate() {
+            let value = alphabet.iter().position(|a| a == c)? as u32;
+            n |= value << (18 - 6 * i);
+        }
+        for i in 0..chunk.len() - 1 {
+            bytes.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Some(bytes)
+}
@@ -1,5 +1,6 @@
-use crate::{AnyValue, Deserialize, Deserializer};
+use crate::{AnyValue, BytesEncoding, Deserialize, DeserializeError, Deserializer, DuplicateKeys};
 use std::borrow::Cow;
+use std::convert::TryFrom;
 use std::iter::Peekable;
 use std::str::CharIndices;
 
@@ -10,6 +11,9 @@ pub struct JSONDeserializer<'a, CONTEXT> {
     recursive_depth: usize,
     source: &'a str,
     iter: Peekable<CharIndices<'a>>,
+    bytes_encoding: BytesEncoding,
+    duplicate_keys: DuplicateKeys,
+    error: Option<DeserializeError>,
     context: CONTEXT,
 }
 
@@ -19,6 +23,9 @@ impl<'a> JSONDeserializer<'a, ()> {
             recursive_depth: 0,
             iter: source.char_indices().peekable(),
             source,
+            bytes_encoding: BytesEncoding::default(),
+            duplicate_keys: DuplicateKeys::default(),
+            error: None,
             context: (),
         }
     }
@@ -33,15 +40,16 @@ impl<'a, CONTEXT> Deserializer<'a> for JSONDeserializer<'a, CONTEXT> {
 
     fn bool(&mut self) -> Option<bool> {
         self.skip_whitespace();
+        // The first character has already been consumed.
         Some(match self.iter.next()?.1 {
             't' => {
-                for _ in 0..4 {
+                for _ in 0..3 {
                     self.iter.next()?;
                 }
                 true
             }
             'f' => {
-                for _ in 0..5 {
+                for _ in 0..4 {
                     self.iter.next()?;
                 }
                 false
@@ -52,7 +60,22 @@ impl<'a, CONTEXT> Deserializer<'a> for JSONDeserializer<'a, CONTEXT> {
 
     fn i64(&mut self) -> Option<i64> {
         self.skip_whitespace();
-        self.parse_number().map(|f| f as i64)
+        self.parse_i64()
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.skip_whitespace();
+        self.parse_u64()
+    }
+
+    fn i128(&mut self) -> Option<i128> {
+        self.skip_whitespace();
+        self.parse_i128()
+    }
+
+    fn u128(&mut self) -> Option<u128> {
+        self.skip_whitespace();
+        self.parse_u128()
     }
 
     fn f64(&mut self) -> Option<f64> {
@@ -60,6 +83,25 @@ impl<'a, CONTEXT> Deserializer<'a> for JSONDeserializer<'a, CONTEXT> {
         self.parse_number()
     }
 
+    fn bytes(&mut self) -> Option<Cow<'a, [u8]>> {
+        self.skip_whitespace();
+        let s = self.parse_string()?;
+        self.bytes_encoding.decode(&s).map(Cow::Owned)
+    }
+
+    fn null(&mut self) -> bool {
+        self.skip_whitespace();
+        match self.iter.peek() {
+            Some((i, 'n')) if self.source[*i..].starts_with("null") => {
+                for _ in 0..4 {
+                    self.iter.next();
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
     fn any<'b>(&'b mut self) -> Option<AnyValue<'a>> {
         self.skip_whitespace();
 
@@ -105,8 +147,8 @@ impl<'a, CONTEXT> Deserializer<'a> for JSONDeserializer<'a, CONTEXT> {
                 }
                 AnyValue::Null
             }
-            '-' => AnyValue::Number(self.parse_number()?), // Parse negative number
-            c if c.is_ascii_digit() => AnyValue::Number(self.parse_number()?),
+            '-' => self.parse_any_number()?, // Parse negative number
+            c if c.is_ascii_digit() => self.parse_any_number()?,
             _ => return None,
         })
     }
@@ -197,9 +239,41 @@ impl<'a, CONTEXT> Deserializer<'a> for JSONDeserializer<'a, CONTEXT> {
     fn get_context_mut(&mut self) -> &mut Self::Context {
         &mut self.context
     }
+
+    fn set_error(&mut self, error: DeserializeError) {
+        // Keep the first error because later ones are likely caused by it.
+        if self.error.is_none() {
+            self.error = Some(error)
+        }
+    }
+
+    fn error(&self) -> Option<&DeserializeError> {
+        self.error.as_ref()
+    }
+
+    fn duplicate_keys(&self) -> DuplicateKeys {
+        self.duplicate_keys
+    }
+
+    fn position(&mut self) -> Option<usize> {
+        self.skip_whitespace();
+        Some(self.iter.peek().map_or(self.source.len(), |(i, _)| *i))
+    }
 }
 
 impl<'a, CONTEXT> JSONDeserializer<'a, CONTEXT> {
+    /// Set how byte arrays are expected to be encoded. By default they're expected as base64.
+    pub fn with_bytes_encoding(mut self, bytes_encoding: BytesEncoding) -> Self {
+        self.bytes_encoding = bytes_encoding;
+        self
+    }
+
+    /// Set how objects that repeat a key are handled. By default later values win.
+    pub fn with_duplicate_keys(mut self, duplicate_keys: DuplicateKeys) -> Self {
+        self.duplicate_keys = duplicate_keys;
+        self
+    }
+
     pub fn skip_whitespace(&mut self) {
         while self.iter.peek().map_or(false, |(_, c)| c.is_whitespace()) {
             self.iter.next();
@@ -279,7 +353,40 @@ impl<'a, CONTEXT> JSONDeserializer<'a, CONTEXT> {
         Some(string)
     }
 
-    pub fn parse_number(&mut self) -> Option<f64> {
+    /// Parses an integer directly from its digits so that values beyond 2^53 stay exact.
+    /// Returns `None` if the integer overflows or if the number has a fraction or exponent.
+    pub fn parse_i64(&mut self) -> Option<i64> {
+        i64::try_from(self.parse_i128()?).ok()
+    }
+
+    /// Like [parse_i64] but for unsigned integers.
+    pub fn parse_u64(&mut self) -> Option<u64> {
+        u64::try_from(self.parse_u128()?).ok()
+    }
+
+    pub fn parse_i128(&mut self) -> Option<i128> {
+        let (is_negative, magnitude) = self.parse_integer_magnitude()?;
+        if is_negative {
+            // The magnitude of `i128::MIN` is one larger than `i128::MAX`.
+            if magnitude > i128::MAX as u128 + 1 {
+                return None;
+            }
+            Some((magnitude as i128).wrapping_neg())
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
+    pub fn parse_u128(&mut self) -> Option<u128> {
+        let (is_negative, magnitude) = self.parse_integer_magnitude()?;
+        // Allow "-0" but no other negative numbers.
+        if is_negative && magnitude != 0 {
+            return None;
+        }
+        Some(magnitude)
+    }
+
+    fn parse_integer_magnitude(&mut self) -> Option<(bool, u128)> {
         let is_negative = match self.iter.peek() {
             Some((_, '-')) => {
                 self.iter.next();
@@ -288,102 +395,82 @@ impl<'a, CONTEXT> JSONDeserializer<'a, CONTEXT> {
             _ => false,
         };
 
-        let mut number = 0.0;
-
+        let mut magnitude: u128 = 0;
         match self.iter.peek()?.1 {
             '0' => {
                 self.iter.next();
             }
-            c if c.is_ascii_digit() => loop {
-                if let Some((_, c)) = self.iter.peek().cloned() {
-                    if let Some(digit) = c.to_digit(10) {
-                        number *= 10.;
-                        number += digit as f64;
-                        self.iter.next();
-                        continue;
-                    }
+            c if c.is_ascii_digit() => {
+                while let Some(digit) = self.iter.peek().and_then(|(_, c)| c.to_digit(10)) {
+                    magnitude = magnitude.checked_mul(10)?.checked_add(digit as u128)?;
+                    self.iter.next();
                 }
-                break;
-            },
+            }
             _ => return None,
         }
 
-        let mut position = 10.0;
-        // Parse fraction
+        // A fraction or exponent means this isn't an integer.
         match self.iter.peek() {
-            Some((_, '.')) => {
+            Some((_, '.')) | Some((_, 'e')) | Some((_, 'E')) => None,
+            _ => Some((is_negative, magnitude)),
+        }
+    }
+
+    /// Parses a number as an exact integer if possible, otherwise as a float.
+    fn parse_any_number(&mut self) -> Option<AnyValue<'a>> {
+        let start = self.iter.clone();
+        if let Some(i) = self.parse_i128() {
+            return Some(AnyValue::Integer(i));
+        }
+        self.iter = start.clone();
+        if let Some(u) = self.parse_u128() {
+            return Some(AnyValue::UInteger(u));
+        }
+        self.iter = start;
+        self.parse_number().map(AnyValue::Number)
+    }
+
+    /// Parses a number by validating it against JSON's grammar and then converting the
+    /// text with Rust's float parsing, which rounds correctly.
+    pub fn parse_number(&mut self) -> Option<f64> {
+        let start = self.iter.peek()?.0;
+        if let Some((_, '-')) = self.iter.peek() {
+            self.iter.next();
+        }
+
+        match self.iter.peek()?.1 {
+            '0' => {
                 self.iter.next();
-                // Parse fraction
-                loop {
-                    if let Some((_, c)) = self.iter.peek().cloned() {
-                        if let Some(digit) = c.to_digit(10) {
-                            number += digit as f64 / position;
-                            position *= 10.0;
-                            self.iter.next();
-                            continue;
-                        }
-                    }
-                    break;
-                }
             }
-            _ => {}
+            c if c.is_ascii_digit() => self.skip_digits(),
+            _ => return None,
+        }
+
+        // Parse fraction
+        if let Some((_, '.')) = self.iter.peek() {
+            self.iter.next();
+            self.iter.peek()?.1.is_ascii_digit().then_some(())?;
+            self.skip_digits();
         }
 
         // Parse exponent
-        match self.iter.peek() {
-            Some((_, 'e')) | Some((_, 'E')) => {
+        if let Some((_, 'e')) | Some((_, 'E')) = self.iter.peek() {
+            self.iter.next();
+            if let Some((_, '-')) | Some((_, '+')) = self.iter.peek() {
                 self.iter.next();
-                let sign = match self.iter.peek()?.1 {
-                    '-' => {
-                        self.iter.next();
-                        -1.
-                    }
-                    '+' => {
-                        self.iter.next();
-                        1.
-                    }
-                    _ => 1.,
-                };
-
-                // Skip leading zeroes
-                loop {
-                    match self.iter.peek() {
-                        Some((_, '0')) => {
-                            self.iter.next();
-                        }
-                        _ => break,
-                    }
-                }
-
-                let mut exponent = 0.0;
-                match self.iter.peek()?.1 {
-                    '0' => {
-                        self.iter.next();
-                    }
-                    c if c.is_ascii_digit() => loop {
-                        if let Some((_, c)) = self.iter.peek().cloned() {
-                            if let Some(digit) = c.to_digit(10) {
-                                exponent *= 10.;
-                                exponent += digit as f64;
-                                self.iter.next();
-                                continue;
-                            }
-                        }
-                        break;
-                    },
-                    _ => return None,
-                }
-
-                number = number * (10.0f64).powf(exponent * sign);
             }
-            _ => {}
+            self.iter.peek()?.1.is_ascii_digit().then_some(())?;
+            self.skip_digits();
         }
 
-        if is_negative {
-            number *= -1.0;
-        }
+        let end = self.iter.peek().map_or(self.source.len(), |(i, _)| *i);
+        self.source[start..end].parse().ok()
+    }
 
-        Some(number)
+    fn skip_digits(&mut self) {
+        while self.iter.peek().is_some_and(|(_, c)| c.is_ascii_digit()) {
+            self.iter.next();
+        }
     }
 }
 
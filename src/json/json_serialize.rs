@@ -1,13 +1,28 @@
 use crate::*;
 use std::iter::Iterator;
+use std::ops::Range;
 
 pub struct JSONSerializer<CONTEXT> {
     s: String,
     indentation: u16,
     just_began_object_or_array: bool,
+    bytes_encoding: BytesEncoding,
+    canonical: bool,
+    sort_keys: bool,
+    /// Objects written in canonical mode since the outermost one began, in the order
+    /// they began. Their text stays unsorted in `s` until the outermost one ends.
+    canonical_objects: Vec<CanonicalObject>,
+    /// Indices in `canonical_objects` of the objects that haven't ended yet.
+    open_canonical_objects: Vec<usize>,
     context: CONTEXT,
 }
 
+/// Where an object written in canonical mode and its property values are in the output.
+struct CanonicalObject {
+    range: Range<usize>,
+    properties: Vec<(String, Range<usize>)>,
+}
+
 impl JSONSerializer<()> {
     pub fn new() -> Self {
         Self::new_with_context(())
@@ -20,10 +35,39 @@ impl<CONTEXT> JSONSerializer<CONTEXT> {
             s: String::new(),
             indentation: 0,
             just_began_object_or_array: false,
+            bytes_encoding: BytesEncoding::default(),
+            canonical: false,
+            sort_keys: false,
+            canonical_objects: Vec::new(),
+            open_canonical_objects: Vec::new(),
             context,
         }
     }
 
+    /// Set how byte arrays are encoded. By default they're encoded as base64.
+    pub fn with_bytes_encoding(mut self, bytes_encoding: BytesEncoding) -> Self {
+        self.bytes_encoding = bytes_encoding;
+        self
+    }
+
+    /// Sort the entries of maps like `HashMap` and objects in `Thing` by key,
+    /// so that serializing the same data always produces the same output.
+    pub fn with_sorted_keys(mut self, sort_keys: bool) -> Self {
+        self.sort_keys = sort_keys;
+        self
+    }
+
+    /// Write [RFC 8785](https://tools.ietf.org/html/rfc8785) canonical JSON (JCS).
+    ///
+    /// Object properties are sorted by their UTF-16 code units, there's no whitespace and
+    /// numbers are formatted like ECMAScript does. As in ECMAScript, all numbers are
+    /// written as `f64`, so integers beyond 2^53 lose precision, and non-finite
+    /// numbers are written as `null`.
+    pub fn with_canonical(mut self, canonical: bool) -> Self {
+        self.canonical = canonical;
+        self
+    }
+
     fn indent(&mut self) {
         self.s.extend((0..self.indentation).map(|_| ' '))
     }
@@ -34,11 +78,43 @@ impl<CONTEXT> Serializer for JSONSerializer<CONTEXT> {
     type Result = String;
 
     fn f64(&mut self, n: f64) {
-        self.s.push_str(&n.to_string())
+        if self.canonical {
+            write_ecmascript_number(n, &mut self.s)
+        } else {
+            self.s.push_str(&n.to_string())
+        }
     }
 
     fn i64(&mut self, n: i64) {
-        self.s.push_str(&n.to_string())
+        if self.canonical {
+            self.f64(n as f64)
+        } else {
+            self.s.push_str(&n.to_string())
+        }
+    }
+
+    fn u64(&mut self, n: u64) {
+        if self.canonical {
+            self.f64(n as f64)
+        } else {
+            self.s.push_str(&n.to_string())
+        }
+    }
+
+    fn i128(&mut self, n: i128) {
+        if self.canonical {
+            self.f64(n as f64)
+        } else {
+            self.s.push_str(&n.to_string())
+        }
+    }
+
+    fn u128(&mut self, n: u128) {
+        if self.canonical {
+            self.f64(n as f64)
+        } else {
+            self.s.push_str(&n.to_string())
+        }
     }
 
     fn bool(&mut self, b: bool) {
@@ -51,7 +127,7 @@ impl<CONTEXT> Serializer for JSONSerializer<CONTEXT> {
 
     fn string(&mut self, s: &str) {
         self.s.push('\"');
-        self.s.push_str(s);
+        write_escaped(s, &mut self.s);
         self.s.push('\"');
     }
 
@@ -59,22 +135,51 @@ impl<CONTEXT> Serializer for JSONSerializer<CONTEXT> {
         self.s.push_str("null");
     }
 
+    fn bytes(&mut self, b: &[u8]) {
+        self.s.push('\"');
+        self.bytes_encoding.encode(b, &mut self.s);
+        self.s.push('\"');
+    }
+
     fn done(self) -> Self::Result {
         self.s
     }
 
     fn begin_array(&mut self) {
-        self.s.push_str("[");
+        self.s.push('[');
         self.just_began_object_or_array = true;
     }
 
     fn begin_object(&mut self) {
+        if self.canonical {
+            let start = self.s.len();
+            self.open_canonical_objects
+                .push(self.canonical_objects.len());
+            self.canonical_objects.push(CanonicalObject {
+                range: start..start,
+                properties: Vec::new(),
+            });
+            // Gives every object a different start. It's replaced when the object is sorted.
+            self.s.push('{');
+            return;
+        }
         self.s.push('{');
         self.indentation += 4;
         self.just_began_object_or_array = true;
     }
 
     fn property<V: Serialize<Self>>(&mut self, name: &str, value: &V) {
+        if self.canonical {
+            let start = self.s.len();
+            value.serialize(self);
+            if let Some(&object) = self.open_canonical_objects.last() {
+                let value = start..self.s.len();
+                self.canonical_objects[object]
+                    .properties
+                    .push((name.to_string(), value));
+            }
+            return;
+        }
         if !self.just_began_object_or_array {
             self.s.push(',');
         }
@@ -87,15 +192,30 @@ impl<CONTEXT> Serializer for JSONSerializer<CONTEXT> {
     }
 
     fn end_object(&mut self) {
+        if self.canonical {
+            if let Some(object) = self.open_canonical_objects.pop() {
+                self.canonical_objects[object].range.end = self.s.len();
+                // Nested objects are sorted along with the outermost one, so each
+                // object's text is only copied once.
+                if self.open_canonical_objects.is_empty() {
+                    let objects = std::mem::take(&mut self.canonical_objects);
+                    let text = self.s.split_off(objects[0].range.start);
+                    write_canonical_object(0, &objects, &text, &mut self.s);
+                }
+            }
+            self.just_began_object_or_array = false;
+            return;
+        }
         self.indentation -= 4;
         self.s.push('\n');
         self.indent();
         self.s.push('}');
+        self.just_began_object_or_array = false;
     }
 
     fn value<V: Serialize<Self>>(&mut self, value: &V) {
         if !self.just_began_object_or_array {
-            self.s += ", "
+            self.s += if self.canonical { "," } else { ", " }
         }
         self.just_began_object_or_array = false;
         value.serialize(self);
@@ -103,6 +223,7 @@ impl<CONTEXT> Serializer for JSONSerializer<CONTEXT> {
 
     fn end_array(&mut self) {
         self.s.push(']');
+        self.just_began_object_or_array = false;
     }
 
     fn get_context(&self) -> &Self::Context {
@@ -112,10 +233,112 @@ impl<CONTEXT> Serializer for JSONSerializer<CONTEXT> {
     fn get_context_mut(&mut self) -> &mut Self::Context {
         &mut self.context
     }
+
+    fn sort_keys(&self) -> bool {
+        self.sort_keys
+    }
+}
+
+/// Write `objects[index]` with its properties sorted by their UTF-16 code units.
+/// `text` is the unsorted output starting at the first object.
+fn write_canonical_object(index: usize, objects: &[CanonicalObject], text: &str, out: &mut String) {
+    let base = objects[0].range.start;
+    let mut properties: Vec<_> = objects[index].properties.iter().collect();
+    properties.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+    out.push('{');
+    for (i, (name, value)) in properties.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push('"');
+        write_escaped(name, out);
+        out.push_str("\":");
+        // Copy the value, writing the objects inside it as they're reached.
+        let mut position = value.start;
+        while position < value.end {
+            let next = objects.partition_point(|o| o.range.start < position);
+            match objects.get(next).filter(|o| o.range.start < value.end) {
+                Some(object) => {
+                    out.push_str(&text[position - base..object.range.start - base]);
+                    write_canonical_object(next, objects, text, out);
+                    position = object.range.end;
+                }
+                None => {
+                    out.push_str(&text[position - base..value.end - base]);
+                    position = value.end;
+                }
+            }
+        }
+    }
+    out.push('}');
+}
+
+/// Escape a string's contents with the fewest escapes JSON allows.
+fn write_escaped(s: &str, out: &mut String) {
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\x08' => out.push_str("\\b"),
+            '\x0C' => out.push_str("\\f"),
+            c if c < ' ' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Format a number the way ECMAScript's `Number.prototype.toString` does,
+/// which is what RFC 8785 requires.
+fn write_ecmascript_number(n: f64, out: &mut String) {
+    if !n.is_finite() {
+        out.push_str("null");
+        return;
+    }
+    if n == 0.0 {
+        out.push('0');
+        return;
+    }
+    if n < 0.0 {
+        out.push('-');
+    }
+
+    // Rust's exponential formatting gives the shortest digits that round trip.
+    let formatted = format!("{:e}", n.abs());
+    let (mantissa, exponent) = formatted.split_at(formatted.find('e').unwrap());
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    // The position of the decimal point relative to the start of the digits.
+    let point = exponent[1..].parse::<i32>().unwrap() + 1;
+
+    if k <= point && point <= 21 {
+        out.push_str(&digits);
+        out.extend((k..point).map(|_| '0'));
+    } else if 0 < point && point <= 21 {
+        out.push_str(&digits[..point as usize]);
+        out.push('.');
+        out.push_str(&digits[point as usize..]);
+    } else if -6 < point && point <= 0 {
+        out.push_str("0.");
+        out.extend((point..0).map(|_| '0'));
+        out.push_str(&digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        out.push_str(&format!("e{:+}", point - 1));
+    }
 }
 
 pub trait ToJson: Sized {
     fn to_json(&self) -> String;
+    /// Serialize to [RFC 8785](https://tools.ietf.org/html/rfc8785) canonical JSON.
+    /// See [JSONSerializer::with_canonical].
+    fn to_canonical_json(&self) -> String;
 }
 impl<T: Serialize<JSONSerializer<()>>> ToJson for T {
     fn to_json(&self) -> String {
@@ -123,4 +346,10 @@ impl<T: Serialize<JSONSerializer<()>>> ToJson for T {
         self.serialize(&mut serializer);
         serializer.done()
     }
+
+    fn to_canonical_json(&self) -> String {
+        let mut serializer = JSONSerializer::new().with_canonical(true);
+        self.serialize(&mut serializer);
+        serializer.done()
+    }
 }
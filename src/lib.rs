@@ -1,17 +1,35 @@
 //! A minimalist serialization / deserialization crate.
 //! **WORK IN PROGRESS**
 
+mod bytes;
 mod deserialize_trait;
+mod map_key;
+mod ordered_map;
+mod patch;
 mod serialize_trait;
 mod thing;
+mod thing_compare;
+mod thing_deserialize;
+mod thing_serialize;
+mod transcode;
 
+pub use bytes::*;
 pub use deserialize_trait::*;
+pub use map_key::*;
+pub use ordered_map::*;
+pub use patch::*;
 pub use serialize_trait::*;
 pub use thing::*;
+pub use thing_compare::*;
+pub use thing_deserialize::*;
+pub use thing_serialize::*;
+pub use transcode::*;
 
 mod json {
+    mod json_bytes;
     mod json_deserialize;
     mod json_serialize;
+    pub use json_bytes::*;
     pub use json_deserialize::*;
     pub use json_serialize::*;
 }
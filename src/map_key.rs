@@ -0,0 +1,159 @@
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+/// The key of a map entry.
+///
+/// Formats that only support string keys, like JSON, convert keys to strings.
+/// Other formats can store the key natively.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MapKey<'a> {
+    String(Cow<'a, str>),
+    Bool(bool),
+    Char(char),
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+}
+
+impl<'a> MapKey<'a> {
+    /// Get the key as a string, converting it if it isn't already one.
+    pub fn to_str(&self) -> Cow<'_, str> {
+        match self {
+            MapKey::String(s) => Cow::Borrowed(s),
+            MapKey::Bool(b) => Cow::Owned(b.to_string()),
+            MapKey::Char(c) => Cow::Owned(c.to_string()),
+            MapKey::I64(i) => Cow::Owned(i.to_string()),
+            MapKey::U64(u) => Cow::Owned(u.to_string()),
+            MapKey::I128(i) => Cow::Owned(i.to_string()),
+            MapKey::U128(u) => Cow::Owned(u.to_string()),
+        }
+    }
+}
+
+/// Implemented for values that can be used as the key of a map.
+///
+/// Only scalar values can be keys. Implement this for newtype wrappers or
+/// unit-only enums by returning the key of the wrapped value or variant.
+pub trait SerializeKey {
+    fn to_key(&self) -> MapKey<'_>;
+}
+
+/// The counterpart of [SerializeKey].
+///
+/// Keys may arrive as strings even if they were serialized as another type,
+/// so implementations should accept both.
+pub trait DeserializeKey<'a>: Sized {
+    fn from_key(key: MapKey<'a>) -> Option<Self>;
+}
+
+impl<K: SerializeKey + ?Sized> SerializeKey for &K {
+    fn to_key(&self) -> MapKey<'_> {
+        (**self).to_key()
+    }
+}
+
+impl SerializeKey for str {
+    fn to_key(&self) -> MapKey<'_> {
+        MapKey::String(Cow::Borrowed(self))
+    }
+}
+
+impl SerializeKey for String {
+    fn to_key(&self) -> MapKey<'_> {
+        MapKey::String(Cow::Borrowed(self))
+    }
+}
+
+impl SerializeKey for Cow<'_, str> {
+    fn to_key(&self) -> MapKey<'_> {
+        MapKey::String(Cow::Borrowed(self))
+    }
+}
+
+impl SerializeKey for bool {
+    fn to_key(&self) -> MapKey<'_> {
+        MapKey::Bool(*self)
+    }
+}
+
+impl SerializeKey for char {
+    fn to_key(&self) -> MapKey<'_> {
+        MapKey::Char(*self)
+    }
+}
+
+impl<'a> DeserializeKey<'a> for String {
+    fn from_key(key: MapKey<'a>) -> Option<Self> {
+        Some(key.to_str().into_owned())
+    }
+}
+
+impl<'a> DeserializeKey<'a> for Cow<'a, str> {
+    fn from_key(key: MapKey<'a>) -> Option<Self> {
+        match key {
+            MapKey::String(s) => Some(s),
+            key => Some(Cow::Owned(key.to_str().into_owned())),
+        }
+    }
+}
+
+impl<'a> DeserializeKey<'a> for bool {
+    fn from_key(key: MapKey<'a>) -> Option<Self> {
+        match key {
+            MapKey::Bool(b) => Some(b),
+            MapKey::String(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> DeserializeKey<'a> for char {
+    fn from_key(key: MapKey<'a>) -> Option<Self> {
+        match key {
+            MapKey::Char(c) => Some(c),
+            MapKey::String(s) => {
+                let mut chars = s.chars();
+                let c = chars.next()?;
+                if chars.next().is_none() {
+                    Some(c)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Integers convert to the widest key of the same signedness.
+/// Out of range keys fail to deserialize instead of wrapping.
+macro_rules! integer_key {
+    ($($t:ty => $variant:ident),*) => {
+        $(
+            impl SerializeKey for $t {
+                fn to_key(&self) -> MapKey<'_> {
+                    MapKey::$variant(*self as _)
+                }
+            }
+
+            impl<'a> DeserializeKey<'a> for $t {
+                fn from_key(key: MapKey<'a>) -> Option<Self> {
+                    match key {
+                        MapKey::String(s) => s.parse().ok(),
+                        MapKey::I64(i) => <$t>::try_from(i).ok(),
+                        MapKey::U64(u) => <$t>::try_from(u).ok(),
+                        MapKey::I128(i) => <$t>::try_from(i).ok(),
+                        MapKey::U128(u) => <$t>::try_from(u).ok(),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integer_key!(
+    i8 => I64, i16 => I64, i32 => I64, i64 => I64, isize => I64, i128 => I128,
+    u8 => U64, u16 => U64, u32 => U64, u64 => U64, usize => U64, u128 => U128
+);
@@ -0,0 +1,274 @@
+use crate::{pointer_index, pointer_tokens, thing, OrderedMap, ThingOwned};
+
+/// Why a JSON Patch operation failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchErrorKind {
+    /// The patch isn't an array of operation objects, or an operation is missing a member.
+    MalformedOperation,
+    /// The `op` member isn't one of the six operations.
+    UnknownOperation,
+    /// The location doesn't exist or isn't a valid JSON Pointer.
+    PathNotFound,
+    /// A value can't be moved into one of its own children.
+    MoveIntoChild,
+    /// A `test` operation's value didn't match.
+    TestFailed,
+}
+
+/// An error applying a JSON Patch. No changes are made when a patch fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchError {
+    /// The index of the failing operation in the patch.
+    pub index: usize,
+    /// The operation's `op`, or an empty string if it didn't have one.
+    pub op: String,
+    /// The JSON Pointer the operation failed on. This is its `from` if a `move` or `copy`
+    /// couldn't find the source value, otherwise its `path`.
+    pub pointer: String,
+    pub kind: PatchErrorKind,
+}
+
+impl std::fmt::Display for PatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reason = match self.kind {
+            PatchErrorKind::MalformedOperation => "malformed operation",
+            PatchErrorKind::UnknownOperation => "unknown operation",
+            PatchErrorKind::PathNotFound => "path not found",
+            PatchErrorKind::MoveIntoChild => "cannot move a value into one of its children",
+            PatchErrorKind::TestFailed => "test failed",
+        };
+        write!(
+            f,
+            "patch operation {} (\"{}\" at \"{}\"): {}",
+            self.index, self.op, self.pointer, reason
+        )
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+impl ThingOwned {
+    /// Apply an [RFC 7396](https://tools.ietf.org/html/rfc7396) JSON Merge Patch.
+    ///
+    /// Properties in the patch replace properties here, `null` properties are removed
+    /// and objects are merged recursively.
+    pub fn merge_patch(&mut self, patch: &ThingOwned) {
+        match patch {
+            ThingOwned::Object(patch) => {
+                if !self.is_object() {
+                    *self = ThingOwned::Object(OrderedMap::new());
+                }
+                for (key, value) in patch.iter() {
+                    if value.is_null() {
+                        self.remove(key);
+                    } else {
+                        self.entry(key.as_str()).merge_patch(value);
+                    }
+                }
+            }
+            _ => *self = patch.clone(),
+        }
+    }
+
+    /// Apply an [RFC 6902](https://tools.ietf.org/html/rfc6902) JSON Patch,
+    /// an array of `add`, `remove`, `replace`, `move`, `copy` and `test` operations.
+    ///
+    /// The patch is applied atomically: if any operation fails nothing is changed.
+    pub fn apply_patch(&mut self, patch: &ThingOwned) -> Result<(), PatchError> {
+        let operations = patch.array().ok_or(PatchError {
+            index: 0,
+            op: String::new(),
+            pointer: String::new(),
+            kind: PatchErrorKind::MalformedOperation,
+        })?;
+
+        let mut result = self.clone();
+        for (index, operation) in operations.iter().enumerate() {
+            let op = operation.get_str("op").unwrap_or("");
+            apply_operation(&mut result, operation).map_err(|(pointer, kind)| PatchError {
+                index,
+                op: op.to_string(),
+                pointer: pointer.to_string(),
+                kind,
+            })?;
+        }
+        *self = result;
+        Ok(())
+    }
+}
+
+/// Returns the pointer that failed along with why, so errors can name `from` when
+/// that's the problem.
+fn apply_operation<'o>(
+    target: &mut ThingOwned,
+    operation: &'o ThingOwned,
+) -> Result<(), (&'o str, PatchErrorKind)> {
+    let path = operation.get_str("path");
+    let at_path = |kind| (path.unwrap_or(""), kind);
+    let malformed = || at_path(PatchErrorKind::MalformedOperation);
+    let member = |name| operation.get(name).ok_or_else(malformed);
+    let op = operation.get_str("op").ok_or_else(malformed)?;
+    let path = path.ok_or_else(malformed)?;
+    let from = || operation.get_str("from").ok_or_else(malformed);
+
+    match op {
+        "add" => add(target, path, member("value")?.clone()).map_err(at_path),
+        "remove" => remove(target, path).map(|_| ()).map_err(at_path),
+        "replace" => {
+            let value = member("value")?.clone();
+            *target
+                .pointer_mut(path)
+                .ok_or_else(|| at_path(PatchErrorKind::PathNotFound))? = value;
+            Ok(())
+        }
+        "move" => {
+            let from = from()?;
+            if path.starts_with(from) && path[from.len()..].starts_with('/') {
+                return Err(at_path(PatchErrorKind::MoveIntoChild));
+            }
+            let value = remove(target, from).map_err(|kind| (from, kind))?;
+            add(target, path, value).map_err(at_path)
+        }
+        "copy" => {
+            let from = from()?;
+            let value = target
+                .pointer(from)
+                .ok_or((from, PatchErrorKind::PathNotFound))?;
+            add(target, path, value.clone()).map_err(at_path)
+        }
+        "test" => {
+            let value = target
+                .pointer(path)
+                .ok_or_else(|| at_path(PatchErrorKind::PathNotFound))?;
+            if value == member("value")? {
+                Ok(())
+            } else {
+                Err(at_path(PatchErrorKind::TestFailed))
+            }
+        }
+        _ => Err(at_path(PatchErrorKind::UnknownOperation)),
+    }
+}
+
+/// Split a JSON Pointer into the pointer to its parent and its unescaped last token.
+/// Returns `None` for the whole document.
+fn split_pointer(pointer: &str) -> Result<Option<(&str, String)>, PatchErrorKind> {
+    if pointer.is_empty() {
+        return Ok(None);
+    }
+    let split = pointer.rfind('/').ok_or(PatchErrorKind::PathNotFound)?;
+    let last = pointer_tokens(&pointer[split..])
+        .and_then(|mut tokens| tokens.next())
+        .ok_or(PatchErrorKind::PathNotFound)?;
+    Ok(Some((&pointer[..split], last.into_owned())))
+}
+
+fn add(target: &mut ThingOwned, pointer: &str, value: ThingOwned) -> Result<(), PatchErrorKind> {
+    let (parent, last) = match split_pointer(pointer)? {
+        Some(split) => split,
+        None => {
+            *target = value;
+            return Ok(());
+        }
+    };
+    match target
+        .pointer_mut(parent)
+        .ok_or(PatchErrorKind::PathNotFound)?
+    {
+        ThingOwned::Object(o) => {
+            o.insert(last, value);
+        }
+        ThingOwned::Array(a) => {
+            let index = if last == "-" {
+                a.len()
+            } else {
+                pointer_index(&last).ok_or(PatchErrorKind::PathNotFound)?
+            };
+            if index > a.len() {
+                return Err(PatchErrorKind::PathNotFound);
+            }
+            a.insert(index, value);
+        }
+        _ => return Err(PatchErrorKind::PathNotFound),
+    }
+    Ok(())
+}
+
+fn remove(target: &mut ThingOwned, pointer: &str) -> Result<ThingOwned, PatchErrorKind> {
+    let (parent, last) = split_pointer(pointer)?.ok_or(PatchErrorKind::PathNotFound)?;
+    match target
+        .pointer_mut(parent)
+        .ok_or(PatchErrorKind::PathNotFound)?
+    {
+        ThingOwned::Object(o) => o.remove(last.as_str()).ok_or(PatchErrorKind::PathNotFound),
+        ThingOwned::Array(a) => {
+            let index = pointer_index(&last).ok_or(PatchErrorKind::PathNotFound)?;
+            (index < a.len())
+                .then(|| a.remove(index))
+                .ok_or(PatchErrorKind::PathNotFound)
+        }
+        _ => Err(PatchErrorKind::PathNotFound),
+    }
+}
+
+/// Produce a JSON Patch that turns `a` into `b` when applied with [ThingOwned::apply_patch].
+///
+/// Objects are compared property by property and arrays index by index, so only the
+/// values that changed are replaced. Moved or reordered array elements are not detected.
+pub fn diff(a: &ThingOwned, b: &ThingOwned) -> ThingOwned {
+    let mut patch = Vec::new();
+    diff_into(a, b, &mut String::new(), &mut patch);
+    ThingOwned::Array(patch)
+}
+
+fn diff_into(a: &ThingOwned, b: &ThingOwned, pointer: &mut String, patch: &mut Vec<ThingOwned>) {
+    match (a, b) {
+        (ThingOwned::Object(a), ThingOwned::Object(b)) => {
+            for (key, a) in a.iter() {
+                let length = pointer.len();
+                push_token(pointer, key);
+                match b.get(key.as_str()) {
+                    Some(b) => diff_into(a, b, pointer, patch),
+                    None => patch.push(thing!({"op": "remove", "path": pointer.as_str()})),
+                }
+                pointer.truncate(length);
+            }
+            for (key, b) in b.iter() {
+                if !a.contains_key(key.as_str()) {
+                    let length = pointer.len();
+                    push_token(pointer, key);
+                    patch.push(thing!({"op": "add", "path": pointer.as_str(), "value": b.clone()}));
+                    pointer.truncate(length);
+                }
+            }
+        }
+        (ThingOwned::Array(a), ThingOwned::Array(b)) => {
+            let length = pointer.len();
+            for (i, (a, b)) in a.iter().zip(b.iter()).enumerate() {
+                pointer.push_str(&format!("/{}", i));
+                diff_into(a, b, pointer, patch);
+                pointer.truncate(length);
+            }
+            // Remove from the end so earlier indices stay valid.
+            for i in (b.len()..a.len()).rev() {
+                patch.push(thing!({"op": "remove", "path": format!("{}/{}", pointer, i)}));
+            }
+            for b in b.iter().skip(a.len()) {
+                patch.push(
+                    thing!({"op": "add", "path": format!("{}/-", pointer), "value": b.clone()}),
+                );
+            }
+        }
+        _ => {
+            if a != b {
+                patch.push(thing!({"op": "replace", "path": pointer.as_str(), "value": b.clone()}));
+            }
+        }
+    }
+}
+
+/// Append an escaped reference token to a JSON Pointer.
+fn push_token(pointer: &mut String, token: &str) {
+    pointer.push('/');
+    pointer.push_str(&token.replace('~', "~0").replace('/', "~1"));
+}
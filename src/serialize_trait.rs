@@ -1,4 +1,20 @@
-use std::collections::HashMap;
+use crate::{MapKey, SerializeKey};
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::convert::TryFrom;
+use std::ffi::{OsStr, OsString};
+use std::marker::PhantomData;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+};
+use std::ops::{Range, RangeInclusive};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub trait Serialize<S: Serializer> {
     fn serialize(&self, serializer: &mut S);
@@ -12,8 +28,38 @@ pub trait Serializer: Sized {
     fn string(&mut self, s: &str);
     fn bool(&mut self, b: bool);
     fn i64(&mut self, i: i64);
+    /// Defaults to `i64`, falling back to `f64` for values outside its range.
+    fn u64(&mut self, u: u64) {
+        match i64::try_from(u) {
+            Ok(i) => self.i64(i),
+            Err(_) => self.f64(u as f64),
+        }
+    }
+    /// Defaults to `i64`, falling back to `f64` for values outside its range.
+    fn i128(&mut self, i: i128) {
+        match i64::try_from(i) {
+            Ok(i) => self.i64(i),
+            Err(_) => self.f64(i as f64),
+        }
+    }
+    /// Defaults to `i64`, falling back to `f64` for values outside its range.
+    fn u128(&mut self, u: u128) {
+        match i64::try_from(u) {
+            Ok(i) => self.i64(i),
+            Err(_) => self.f64(u as f64),
+        }
+    }
     fn f64(&mut self, n: f64);
     fn null(&mut self);
+    /// Serialize a blob of bytes. Text formats may need to encode them.
+    /// Defaults to an array of numbers.
+    fn bytes(&mut self, b: &[u8]) {
+        self.begin_array();
+        for byte in b {
+            self.value(&u64::from(*byte));
+        }
+        self.end_array();
+    }
 
     /// Serialize a value that implements Serialize.
     fn serialize<V: Serialize<Self>>(&mut self, value: &V) {
@@ -25,6 +71,11 @@ pub trait Serializer: Sized {
     fn end_object(&mut self);
     /// Only call this in-between [begin_object] and [end_object] calls
     fn property<V: Serialize<Self>>(&mut self, name: &str, value: &V);
+    /// Like [property] but for map entries whose keys may not be strings.
+    /// By default the key is converted to a string.
+    fn entry<V: Serialize<Self>>(&mut self, key: MapKey, value: &V) {
+        self.property(&key.to_str(), value)
+    }
 
     fn begin_array(&mut self);
     fn end_array(&mut self);
@@ -33,41 +84,63 @@ pub trait Serializer: Sized {
 
     fn get_context(&self) -> &Self::Context;
     fn get_context_mut(&mut self) -> &mut Self::Context;
-}
 
-impl<S: Serializer> Serialize<S> for &str {
-    fn serialize(&self, serializer: &mut S) {
-        serializer.string(self)
+    /// If `true` maps and objects that don't have a meaningful order, like `HashMap`,
+    /// should serialize their entries sorted by key so output is deterministic.
+    fn sort_keys(&self) -> bool {
+        false
     }
 }
 
-impl<S: Serializer> Serialize<S> for String {
+/// Serializes a value with a function instead of its own [Serialize] impl.
+/// The derive uses this for fields with a `serialize_with` or `with` attribute.
+pub struct SerializeWith<'a, T: ?Sized, S>(pub &'a T, pub fn(&T, &mut S));
+
+impl<'a, S: Serializer, T: ?Sized> Serialize<S> for SerializeWith<'a, T, S> {
     fn serialize(&self, serializer: &mut S) {
-        serializer.string(self)
+        (self.1)(self.0, serializer)
     }
 }
 
-impl<S: Serializer> Serialize<S> for i32 {
+impl<S: Serializer, SERIALIZE: Serialize<S> + ?Sized> Serialize<S> for &SERIALIZE {
     #[inline]
     fn serialize(&self, serializer: &mut S) {
-        serializer.i64(*self as i64)
+        (**self).serialize(serializer)
     }
 }
 
-impl<S: Serializer> Serialize<S> for i64 {
-    #[inline]
+impl<S: Serializer> Serialize<S> for str {
     fn serialize(&self, serializer: &mut S) {
-        serializer.i64(*self)
+        serializer.string(self)
     }
 }
 
-impl<S: Serializer> Serialize<S> for usize {
-    #[inline]
+impl<S: Serializer> Serialize<S> for String {
     fn serialize(&self, serializer: &mut S) {
-        serializer.i64(*self as i64)
+        serializer.string(self)
     }
 }
 
+/// Signed integers are widened to `i64` and unsigned integers to `u64`,
+/// unless they're 128 bits.
+macro_rules! serialize_integer {
+    ($($t:ty => $method:ident),*) => {
+        $(
+            impl<S: Serializer> Serialize<S> for $t {
+                #[inline]
+                fn serialize(&self, serializer: &mut S) {
+                    serializer.$method(*self as _)
+                }
+            }
+        )*
+    };
+}
+
+serialize_integer!(
+    i8 => i64, i16 => i64, i32 => i64, i64 => i64, isize => i64, i128 => i128,
+    u8 => u64, u16 => u64, u32 => u64, u64 => u64, usize => u64, u128 => u128
+);
+
 impl<S: Serializer> Serialize<S> for f32 {
     #[inline]
     fn serialize(&self, serializer: &mut S) {
@@ -122,18 +195,166 @@ impl<S: Serializer, SERIALIZE: Serialize<S>> Serialize<S> for Vec<SERIALIZE> {
     }
 }
 
-impl<S: Serializer, STRING: std::ops::Deref<Target = str>, V: Serialize<S>> Serialize<S>
-    for HashMap<STRING, V>
+impl<S: Serializer, K: SerializeKey, V: Serialize<S>, HASHER> Serialize<S>
+    for HashMap<K, V, HASHER>
 {
     fn serialize(&self, serializer: &mut S) {
         serializer.begin_object();
-        for (key, value) in self.into_iter() {
-            serializer.property(key, value);
+        if serializer.sort_keys() {
+            let mut entries: Vec<_> = self.iter().map(|(k, v)| (k.to_key(), v)).collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (key, value) in entries {
+                serializer.entry(key, value);
+            }
+        } else {
+            for (key, value) in self.iter() {
+                serializer.entry(key.to_key(), value);
+            }
+        }
+        serializer.end_object();
+    }
+}
+
+impl<S: Serializer, K: SerializeKey, V: Serialize<S>> Serialize<S> for BTreeMap<K, V> {
+    fn serialize(&self, serializer: &mut S) {
+        serializer.begin_object();
+        for (key, value) in self.iter() {
+            serializer.entry(key.to_key(), value);
         }
         serializer.end_object();
     }
 }
 
+fn serialize_sequence<'a, S: Serializer, SERIALIZE: Serialize<S> + 'a>(
+    serializer: &mut S,
+    values: impl Iterator<Item = &'a SERIALIZE>,
+) {
+    serializer.begin_array();
+    for value in values {
+        serializer.value(value);
+    }
+    serializer.end_array();
+}
+
+impl<S: Serializer, SERIALIZE: Serialize<S>, HASHER> Serialize<S> for HashSet<SERIALIZE, HASHER> {
+    fn serialize(&self, serializer: &mut S) {
+        serialize_sequence(serializer, self.iter())
+    }
+}
+
+impl<S: Serializer, SERIALIZE: Serialize<S>> Serialize<S> for BTreeSet<SERIALIZE> {
+    fn serialize(&self, serializer: &mut S) {
+        serialize_sequence(serializer, self.iter())
+    }
+}
+
+impl<S: Serializer, SERIALIZE: Serialize<S>> Serialize<S> for VecDeque<SERIALIZE> {
+    fn serialize(&self, serializer: &mut S) {
+        serialize_sequence(serializer, self.iter())
+    }
+}
+
+impl<S: Serializer, SERIALIZE: Serialize<S>> Serialize<S> for LinkedList<SERIALIZE> {
+    fn serialize(&self, serializer: &mut S) {
+        serialize_sequence(serializer, self.iter())
+    }
+}
+
+/// Values are serialized in the heap's internal order, not in sorted order.
+impl<S: Serializer, SERIALIZE: Serialize<S>> Serialize<S> for BinaryHeap<SERIALIZE> {
+    fn serialize(&self, serializer: &mut S) {
+        serialize_sequence(serializer, self.iter())
+    }
+}
+
+impl<S: Serializer, SERIALIZE: Serialize<S> + ?Sized> Serialize<S> for Box<SERIALIZE> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<S: Serializer, SERIALIZE: Serialize<S> + ?Sized> Serialize<S> for Rc<SERIALIZE> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<S: Serializer, SERIALIZE: Serialize<S> + ?Sized> Serialize<S> for Arc<SERIALIZE> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<S: Serializer, SERIALIZE: Serialize<S> + ToOwned + ?Sized> Serialize<S>
+    for Cow<'_, SERIALIZE>
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<S: Serializer, SERIALIZE: Serialize<S> + Copy> Serialize<S> for Cell<SERIALIZE> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) {
+        self.get().serialize(serializer)
+    }
+}
+
+/// Panics if the value is currently mutably borrowed.
+impl<S: Serializer, SERIALIZE: Serialize<S> + ?Sized> Serialize<S> for RefCell<SERIALIZE> {
+    fn serialize(&self, serializer: &mut S) {
+        self.borrow().serialize(serializer)
+    }
+}
+
+/// Blocks until the lock is acquired.
+/// A poisoned `Mutex` still serializes the value it contains.
+impl<S: Serializer, SERIALIZE: Serialize<S> + ?Sized> Serialize<S> for Mutex<SERIALIZE> {
+    fn serialize(&self, serializer: &mut S) {
+        match self.lock() {
+            Ok(value) => value.serialize(serializer),
+            Err(poisoned) => poisoned.into_inner().serialize(serializer),
+        }
+    }
+}
+
+impl<S: Serializer> Serialize<S> for () {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) {
+        serializer.null()
+    }
+}
+
+/// Tuples are serialized as fixed-length arrays.
+macro_rules! serialize_tuple {
+    ($($name:ident $index:tt),*) => {
+        impl<S: Serializer, $($name: Serialize<S>),*> Serialize<S> for ($($name,)*) {
+            fn serialize(&self, serializer: &mut S) {
+                serializer.begin_array();
+                $(serializer.value(&self.$index);)*
+                serializer.end_array();
+            }
+        }
+    };
+}
+
+serialize_tuple!(T0 0);
+serialize_tuple!(T0 0, T1 1);
+serialize_tuple!(T0 0, T1 1, T2 2);
+serialize_tuple!(T0 0, T1 1, T2 2, T3 3);
+serialize_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4);
+serialize_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+serialize_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+serialize_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
+serialize_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8);
+serialize_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9);
+serialize_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10);
+serialize_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11);
+
 impl<S: Serializer, SERIALIZE: Serialize<S>> Serialize<S> for Option<SERIALIZE> {
     #[inline]
     fn serialize(&self, serializer: &mut S) {
@@ -144,3 +365,152 @@ impl<S: Serializer, SERIALIZE: Serialize<S>> Serialize<S> for Option<SERIALIZE>
         }
     }
 }
+
+impl<S: Serializer> Serialize<S> for char {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) {
+        serializer.string(self.encode_utf8(&mut [0; 4]))
+    }
+}
+
+impl<S: Serializer> Serialize<S> for Duration {
+    fn serialize(&self, serializer: &mut S) {
+        serializer.begin_object();
+        serializer.property("secs", &self.as_secs());
+        serializer.property("nanos", &self.subsec_nanos());
+        serializer.end_object();
+    }
+}
+
+/// Times before the epoch are serialized with negative seconds,
+/// nanoseconds are always positive.
+impl<S: Serializer> Serialize<S> for SystemTime {
+    fn serialize(&self, serializer: &mut S) {
+        let (secs, nanos) = match self.duration_since(UNIX_EPOCH) {
+            Ok(after) => (after.as_secs() as i64, after.subsec_nanos()),
+            Err(before) => {
+                let before = before.duration();
+                if before.subsec_nanos() == 0 {
+                    (-(before.as_secs() as i64), 0)
+                } else {
+                    (
+                        -(before.as_secs() as i64) - 1,
+                        1_000_000_000 - before.subsec_nanos(),
+                    )
+                }
+            }
+        };
+        serializer.begin_object();
+        serializer.property("secs_since_epoch", &secs);
+        serializer.property("nanos_since_epoch", &nanos);
+        serializer.end_object();
+    }
+}
+
+/// Paths that aren't valid UTF-8 are serialized lossily.
+impl<S: Serializer> Serialize<S> for Path {
+    fn serialize(&self, serializer: &mut S) {
+        serializer.string(&self.to_string_lossy())
+    }
+}
+
+impl<S: Serializer> Serialize<S> for PathBuf {
+    fn serialize(&self, serializer: &mut S) {
+        self.as_path().serialize(serializer)
+    }
+}
+
+/// Strings that aren't valid UTF-8 are serialized lossily.
+impl<S: Serializer> Serialize<S> for OsStr {
+    fn serialize(&self, serializer: &mut S) {
+        serializer.string(&self.to_string_lossy())
+    }
+}
+
+impl<S: Serializer> Serialize<S> for OsString {
+    fn serialize(&self, serializer: &mut S) {
+        self.as_os_str().serialize(serializer)
+    }
+}
+
+/// Serializes types using their `Display` implementation.
+macro_rules! serialize_display {
+    ($($t:ty),*) => {
+        $(
+            impl<S: Serializer> Serialize<S> for $t {
+                fn serialize(&self, serializer: &mut S) {
+                    serializer.string(&self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+serialize_display!(
+    IpAddr,
+    Ipv4Addr,
+    Ipv6Addr,
+    SocketAddr,
+    SocketAddrV4,
+    SocketAddrV6
+);
+
+macro_rules! serialize_non_zero {
+    ($($t:ty),*) => {
+        $(
+            impl<S: Serializer> Serialize<S> for $t {
+                #[inline]
+                fn serialize(&self, serializer: &mut S) {
+                    self.get().serialize(serializer)
+                }
+            }
+        )*
+    };
+}
+
+serialize_non_zero!(
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize
+);
+
+impl<S: Serializer, SERIALIZE: Serialize<S>> Serialize<S> for Wrapping<SERIALIZE> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<S: Serializer, SERIALIZE: Serialize<S>> Serialize<S> for Range<SERIALIZE> {
+    fn serialize(&self, serializer: &mut S) {
+        serializer.begin_object();
+        serializer.property("start", &self.start);
+        serializer.property("end", &self.end);
+        serializer.end_object();
+    }
+}
+
+impl<S: Serializer, SERIALIZE: Serialize<S>> Serialize<S> for RangeInclusive<SERIALIZE> {
+    fn serialize(&self, serializer: &mut S) {
+        serializer.begin_object();
+        serializer.property("start", self.start());
+        serializer.property("end", self.end());
+        serializer.end_object();
+    }
+}
+
+impl<S: Serializer, T: ?Sized> Serialize<S> for PhantomData<T> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) {
+        serializer.null()
+    }
+}
@@ -1,38 +1,43 @@
-use crate::{AnyValue, Deserialize, Deserializer, JSONDeserializer, Serialize, Serializer};
+use crate::{
+    AnyValue, Deserialize, Deserializer, JSONDeserializer, OrderedMap, SeenKeys, Serialize,
+    Serializer,
+};
 use std::borrow::Cow;
-use std::collections::HashMap;
-
-#[derive(Debug)]
-pub struct ObjectProperty<'a> {
-    pub item: Thing<'a>,
-    pub index: usize,
-}
+use std::convert::TryFrom;
+use std::ops::Index;
 
 #[derive(Debug)]
 /// A flexible data structure that everything can deserialize to.
 pub enum Thing<'a> {
     String(Cow<'a, str>),
     Bool(bool),
+    /// An integer that was represented exactly in the source.
+    Integer(i128),
+    /// An integer too large for `Integer`.
+    UInteger(u128),
     Number(f64),
-    Object(HashMap<Cow<'a, str>, ObjectProperty<'a>>),
+    Bytes(Cow<'a, [u8]>),
+    /// Properties are kept in the order they were inserted.
+    Object(OrderedMap<Cow<'a, str>, Thing<'a>>),
     Array(Vec<Thing<'a>>),
     Null,
 }
 
-#[derive(Debug, Clone)]
-pub struct ObjectPropertyOwned {
-    pub item: ThingOwned,
-    pub index: usize,
-}
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 /// A flexible data structure that everything can deserialize to.
 pub enum ThingOwned {
     String(String),
     Bool(bool),
+    /// An integer that was represented exactly in the source.
+    Integer(i128),
+    /// An integer too large for `Integer`.
+    UInteger(u128),
     Number(f64),
-    Object(HashMap<String, ObjectPropertyOwned>),
+    Bytes(Vec<u8>),
+    /// Properties are kept in the order they were inserted.
+    Object(OrderedMap<String, ThingOwned>),
     Array(Vec<ThingOwned>),
+    #[default]
     Null,
 }
 
@@ -42,18 +47,15 @@ impl<'a> Thing<'a> {
         match self {
             Thing::String(s) => ThingOwned::String(s.to_string()),
             Thing::Bool(b) => ThingOwned::Bool(*b),
+            Thing::Integer(i) => ThingOwned::Integer(*i),
+            Thing::UInteger(u) => ThingOwned::UInteger(*u),
             Thing::Number(n) => ThingOwned::Number(*n),
-            Thing::Object(h) => {
-                let mut new_hash_map = HashMap::with_capacity(h.len());
-                for (k, v) in h.iter() {
-                    let object_property_owned = ObjectPropertyOwned {
-                        item: v.item.to_owned(),
-                        index: v.index,
-                    };
-                    new_hash_map.insert(k.to_string(), object_property_owned);
-                }
-                ThingOwned::Object(new_hash_map)
-            }
+            Thing::Bytes(b) => ThingOwned::Bytes(b.to_vec()),
+            Thing::Object(o) => ThingOwned::Object(
+                o.iter()
+                    .map(|(k, v)| (k.to_string(), v.to_owned()))
+                    .collect(),
+            ),
             Thing::Array(a) => ThingOwned::Array(a.iter().map(|a| a.to_owned()).collect()),
             Thing::Null => ThingOwned::Null,
         }
@@ -73,14 +75,32 @@ impl<'a> Thing<'a> {
         }
     }
 
+    /// Get this value as an `f64`. Integers are converted and may lose precision.
     pub fn number(&self) -> Option<f64> {
         match self {
             Thing::Number(v) => Some(*v),
+            Thing::Integer(v) => Some(*v as f64),
+            Thing::UInteger(v) => Some(*v as f64),
             _ => None,
         }
     }
 
-    pub fn object(&self) -> Option<&HashMap<Cow<'a, str>, ObjectProperty<'a>>> {
+    /// Get this value as an `i64` if it's an integer in range.
+    pub fn integer(&self) -> Option<i64> {
+        match self {
+            Thing::Integer(v) => i64::try_from(*v).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn bytes(&self) -> Option<&Cow<'a, [u8]>> {
+        match self {
+            Thing::Bytes(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn object(&self) -> Option<&OrderedMap<Cow<'a, str>, Thing<'a>>> {
         match self {
             Thing::Object(v) => Some(v),
             _ => None,
@@ -95,19 +115,389 @@ impl<'a> Thing<'a> {
     }
 }
 
+impl ThingOwned {
+    pub fn string(&self) -> Option<&String> {
+        match self {
+            ThingOwned::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn bool(&self) -> Option<bool> {
+        match self {
+            ThingOwned::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Get this value as an `f64`. Integers are converted and may lose precision.
+    pub fn number(&self) -> Option<f64> {
+        match self {
+            ThingOwned::Number(v) => Some(*v),
+            ThingOwned::Integer(v) => Some(*v as f64),
+            ThingOwned::UInteger(v) => Some(*v as f64),
+            _ => None,
+        }
+    }
+
+    /// Get this value as an `i64` if it's an integer in range.
+    pub fn integer(&self) -> Option<i64> {
+        match self {
+            ThingOwned::Integer(v) => i64::try_from(*v).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn bytes(&self) -> Option<&Vec<u8>> {
+        match self {
+            ThingOwned::Bytes(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn object(&self) -> Option<&OrderedMap<String, ThingOwned>> {
+        match self {
+            ThingOwned::Object(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn array(&self) -> Option<&Vec<ThingOwned>> {
+        match self {
+            ThingOwned::Array(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl ThingOwned {
+    pub fn object_mut(&mut self) -> Option<&mut OrderedMap<String, ThingOwned>> {
+        match self {
+            ThingOwned::Object(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn array_mut(&mut self) -> Option<&mut Vec<ThingOwned>> {
+        match self {
+            ThingOwned::Array(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Get a mutable property if this is an object.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut ThingOwned> {
+        self.object_mut()?.get_mut(key)
+    }
+
+    /// Get a mutable element if this is an array.
+    pub fn get_index_mut(&mut self, index: usize) -> Option<&mut ThingOwned> {
+        self.array_mut()?.get_mut(index)
+    }
+
+    /// Look up a value by JSON Pointer for editing. See [ThingOwned::pointer].
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut ThingOwned> {
+        let mut current = self;
+        for token in pointer_tokens(pointer)? {
+            current = match current {
+                ThingOwned::Object(o) => o.get_mut(&*token)?,
+                ThingOwned::Array(a) => a.get_mut(pointer_index(&token)?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    /// Sets a property and returns its previous value.
+    /// New properties are added after existing ones.
+    ///
+    /// `Null` is first replaced with an empty object.
+    /// Panics if this is anything else that isn't an object.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<ThingOwned>,
+    ) -> Option<ThingOwned> {
+        self.as_object_or_panic().insert(key.into(), value.into())
+    }
+
+    /// Removes a property if this is an object, preserving the order of the others.
+    pub fn remove(&mut self, key: &str) -> Option<ThingOwned> {
+        self.object_mut()?.remove(key)
+    }
+
+    /// Get a property for editing, inserting `Null` if it doesn't exist.
+    ///
+    /// `Null` is first replaced with an empty object.
+    /// Panics if this is anything else that isn't an object.
+    pub fn entry(&mut self, key: impl Into<String>) -> &mut ThingOwned {
+        self.as_object_or_panic()
+            .get_or_insert_with(key.into(), || ThingOwned::Null)
+    }
+
+    /// Appends a value to an array.
+    ///
+    /// `Null` is first replaced with an empty array.
+    /// Panics if this is anything else that isn't an array.
+    pub fn push(&mut self, value: impl Into<ThingOwned>) {
+        if self.is_null() {
+            *self = ThingOwned::Array(Vec::new());
+        }
+        match self {
+            ThingOwned::Array(a) => a.push(value.into()),
+            _ => panic!("Cannot push to a ThingOwned that is not an array"),
+        }
+    }
+
+    /// Takes this value, leaving `Null` in its place.
+    pub fn take(&mut self) -> ThingOwned {
+        std::mem::take(self)
+    }
+
+    fn as_object_or_panic(&mut self) -> &mut OrderedMap<String, ThingOwned> {
+        if self.is_null() {
+            *self = ThingOwned::Object(OrderedMap::new());
+        }
+        match self {
+            ThingOwned::Object(o) => o,
+            _ => panic!("Cannot insert a property into a ThingOwned that is not an object"),
+        }
+    }
+}
+
+macro_rules! thing_owned_from {
+    ($variant: ident, $($t: ty),*) => {
+        $(
+            impl From<$t> for ThingOwned {
+                fn from(v: $t) -> Self {
+                    ThingOwned::$variant(v.into())
+                }
+            }
+        )*
+    };
+}
+
+thing_owned_from!(Integer, i8, i16, i32, i64, i128, u8, u16, u32, u64);
+thing_owned_from!(Number, f32, f64);
+thing_owned_from!(Bool, bool);
+thing_owned_from!(String, &str, String, Cow<'_, str>);
+thing_owned_from!(Object, OrderedMap<String, ThingOwned>);
+
+/// Pointer sized integers are never wider than 64 bits, so they always fit in an `i128`.
+impl From<usize> for ThingOwned {
+    fn from(v: usize) -> Self {
+        ThingOwned::Integer(v as i128)
+    }
+}
+
+impl From<isize> for ThingOwned {
+    fn from(v: isize) -> Self {
+        ThingOwned::Integer(v as i128)
+    }
+}
+
+impl From<u128> for ThingOwned {
+    fn from(v: u128) -> Self {
+        i128::try_from(v).map_or(ThingOwned::UInteger(v), ThingOwned::Integer)
+    }
+}
+
+impl<T: Into<ThingOwned>> From<Vec<T>> for ThingOwned {
+    fn from(v: Vec<T>) -> Self {
+        ThingOwned::Array(v.into_iter().map(Into::into).collect())
+    }
+}
+
+/// `None` becomes `Null`.
+impl<T: Into<ThingOwned>> From<Option<T>> for ThingOwned {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(ThingOwned::Null, Into::into)
+    }
+}
+
+impl From<()> for ThingOwned {
+    fn from(_: ()) -> Self {
+        ThingOwned::Null
+    }
+}
+
+/// Splits an RFC 6901 JSON Pointer into its unescaped reference tokens.
+/// The empty pointer refers to the whole document and has no tokens.
+pub(crate) fn pointer_tokens(pointer: &str) -> Option<impl Iterator<Item = Cow<'_, str>>> {
+    let rest = if pointer.is_empty() {
+        None
+    } else {
+        Some(pointer.strip_prefix('/')?)
+    };
+    Some(
+        rest.into_iter()
+            .flat_map(|rest| rest.split('/'))
+            .map(|token| {
+                if token.contains('~') {
+                    Cow::Owned(token.replace("~1", "/").replace("~0", "~"))
+                } else {
+                    Cow::Borrowed(token)
+                }
+            }),
+    )
+}
+
+/// Parses a JSON Pointer token as an array index.
+/// Leading zeros are not allowed and `-` (past the end) is never a valid index for reading.
+pub(crate) fn pointer_index(token: &str) -> Option<usize> {
+    let valid = !token.is_empty()
+        && token.bytes().all(|b| b.is_ascii_digit())
+        && (token == "0" || !token.starts_with('0'));
+    valid.then_some(())?;
+    token.parse().ok()
+}
+
+static THING_NULL: Thing<'static> = Thing::Null;
+static THING_OWNED_NULL: ThingOwned = ThingOwned::Null;
+
+/// Indexing, path queries, typed getters and predicates shared by [Thing] and [ThingOwned].
+macro_rules! thing_access {
+    (($($generics: tt)*) $thing: ty, $null: ident) => {
+        impl $($generics)* $thing {
+            pub fn is_null(&self) -> bool {
+                matches!(self, Self::Null)
+            }
+
+            pub fn is_bool(&self) -> bool {
+                matches!(self, Self::Bool(_))
+            }
+
+            /// True for both integers and other numbers.
+            pub fn is_number(&self) -> bool {
+                matches!(self, Self::Integer(_) | Self::UInteger(_) | Self::Number(_))
+            }
+
+            pub fn is_integer(&self) -> bool {
+                matches!(self, Self::Integer(_) | Self::UInteger(_))
+            }
+
+            pub fn is_string(&self) -> bool {
+                matches!(self, Self::String(_))
+            }
+
+            pub fn is_bytes(&self) -> bool {
+                matches!(self, Self::Bytes(_))
+            }
+
+            pub fn is_object(&self) -> bool {
+                matches!(self, Self::Object(_))
+            }
+
+            pub fn is_array(&self) -> bool {
+                matches!(self, Self::Array(_))
+            }
+
+            /// Get this value as a `&str` if it's a string.
+            pub fn as_str(&self) -> Option<&str> {
+                match self {
+                    Self::String(s) => Some(&**s),
+                    _ => None,
+                }
+            }
+
+            /// Get this value as a `u64` if it's a non-negative integer.
+            pub fn unsigned(&self) -> Option<u64> {
+                match self {
+                    Self::Integer(i) => u64::try_from(*i).ok(),
+                    _ => None,
+                }
+            }
+
+            /// Get a property if this is an object.
+            pub fn get(&self, key: &str) -> Option<&Self> {
+                match self {
+                    Self::Object(o) => o.get(key),
+                    _ => None,
+                }
+            }
+
+            /// Get an element if this is an array.
+            pub fn get_index(&self, index: usize) -> Option<&Self> {
+                match self {
+                    Self::Array(a) => a.get(index),
+                    _ => None,
+                }
+            }
+
+            pub fn get_str(&self, key: &str) -> Option<&str> {
+                self.get(key)?.as_str()
+            }
+
+            pub fn get_bool(&self, key: &str) -> Option<bool> {
+                self.get(key)?.bool()
+            }
+
+            pub fn get_i64(&self, key: &str) -> Option<i64> {
+                self.get(key)?.integer()
+            }
+
+            pub fn get_u64(&self, key: &str) -> Option<u64> {
+                self.get(key)?.unsigned()
+            }
+
+            pub fn get_f64(&self, key: &str) -> Option<f64> {
+                self.get(key)?.number()
+            }
+
+            pub fn get_array(&self, key: &str) -> Option<&Vec<Self>> {
+                self.get(key)?.array()
+            }
+
+            /// Look up a value by [RFC 6901](https://tools.ietf.org/html/rfc6901) JSON Pointer,
+            /// for example `"/items/0/name"`. The empty string refers to the whole value.
+            pub fn pointer(&self, pointer: &str) -> Option<&Self> {
+                let mut current = self;
+                for token in pointer_tokens(pointer)? {
+                    current = match current {
+                        Self::Object(o) => o.get(&*token)?,
+                        Self::Array(a) => a.get(pointer_index(&token)?)?,
+                        _ => return None,
+                    };
+                }
+                Some(current)
+            }
+        }
+
+        /// Returns `Null` if this isn't an object or the property doesn't exist.
+        impl $($generics)* Index<&str> for $thing {
+            type Output = Self;
+
+            fn index(&self, key: &str) -> &Self {
+                self.get(key).unwrap_or(&$null)
+            }
+        }
+
+        /// Returns `Null` if this isn't an array or the index is out of bounds.
+        impl $($generics)* Index<usize> for $thing {
+            type Output = Self;
+
+            fn index(&self, index: usize) -> &Self {
+                self.get_index(index).unwrap_or(&$null)
+            }
+        }
+    };
+}
+
+thing_access!((<'a>) Thing<'a>, THING_NULL);
+thing_access!(() ThingOwned, THING_OWNED_NULL);
+
 impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for Thing<'a> {
     fn deserialize(deserializer: &mut D) -> Option<Self> {
         Some(match deserializer.any()? {
             AnyValue::Object => {
-                let mut items = HashMap::new();
+                let mut seen_keys = SeenKeys::new(deserializer);
+                let mut items = OrderedMap::new();
                 while let Some(name) = deserializer.has_property() {
-                    items.insert(
-                        name,
-                        ObjectProperty {
-                            index: items.len(),
-                            item: Thing::deserialize(deserializer)?,
-                        },
-                    );
+                    let keep = seen_keys.check(deserializer, &name)?;
+                    let item = Thing::deserialize(deserializer)?;
+                    if keep {
+                        items.insert(name, item);
+                    }
                 }
                 Thing::Object(items)
             }
@@ -118,7 +508,10 @@ impl<'a, D: Deserializer<'a>> Deserialize<'a, D> for Thing<'a> {
                 }
                 Thing::Array(items)
             }
+            AnyValue::Integer(i) => Thing::Integer(i),
+            AnyValue::UInteger(u) => Thing::UInteger(u),
             AnyValue::Number(n) => Thing::Number(n),
+            AnyValue::Bytes(b) => Thing::Bytes(b),
             AnyValue::Bool(b) => Thing::Bool(b),
             AnyValue::String(s) => Thing::String(s),
             AnyValue::Null => Thing::Null,
@@ -137,13 +530,16 @@ impl<'a, S: Serializer> Serialize<S> for Thing<'a> {
         match self {
             Self::Object(o) => {
                 serializer.begin_object();
-                // This allocation and sorting probably isn't ideal,
-                // The alternative is to use a sorted HashMap when deserializing
-                // into Thing.
-                let mut properties: Vec<_> = o.iter().collect();
-                properties.sort_by_key(|(_, i)| i.index);
-                for (key, value) in o.iter() {
-                    serializer.property(key, &value.item);
+                if serializer.sort_keys() {
+                    let mut properties: Vec<_> = o.iter().collect();
+                    properties.sort_by_key(|(key, _)| *key);
+                    for (key, value) in properties {
+                        serializer.property(key, value);
+                    }
+                } else {
+                    for (key, value) in o.iter() {
+                        serializer.property(key, value);
+                    }
                 }
                 serializer.end_object();
             }
@@ -154,7 +550,10 @@ impl<'a, S: Serializer> Serialize<S> for Thing<'a> {
                 }
                 serializer.end_array();
             }
+            Self::Integer(i) => serializer.i128(*i),
+            Self::UInteger(u) => serializer.u128(*u),
             Self::Number(n) => serializer.f64(*n),
+            Self::Bytes(b) => serializer.bytes(b),
             Self::Bool(b) => serializer.bool(*b),
             Self::String(s) => serializer.string(&s),
             Self::Null => serializer.null(),
@@ -167,13 +566,16 @@ impl<'a, S: Serializer> Serialize<S> for ThingOwned {
         match self {
             Self::Object(o) => {
                 serializer.begin_object();
-                // This allocation and sorting probably isn't ideal,
-                // The alternative is to use a sorted HashMap when deserializing
-                // into Thing.
-                let mut properties: Vec<_> = o.iter().collect();
-                properties.sort_by_key(|(_, i)| i.index);
-                for (key, value) in o.iter() {
-                    serializer.property(key, &value.item);
+                if serializer.sort_keys() {
+                    let mut properties: Vec<_> = o.iter().collect();
+                    properties.sort_by_key(|(key, _)| *key);
+                    for (key, value) in properties {
+                        serializer.property(key, value);
+                    }
+                } else {
+                    for (key, value) in o.iter() {
+                        serializer.property(key, value);
+                    }
                 }
                 serializer.end_object();
             }
@@ -184,7 +586,10 @@ impl<'a, S: Serializer> Serialize<S> for ThingOwned {
                 }
                 serializer.end_array();
             }
+            Self::Integer(i) => serializer.i128(*i),
+            Self::UInteger(u) => serializer.u128(*u),
             Self::Number(n) => serializer.f64(*n),
+            Self::Bytes(b) => serializer.bytes(b),
             Self::Bool(b) => serializer.bool(*b),
             Self::String(s) => serializer.string(&s),
             Self::Null => serializer.null(),
@@ -205,3 +610,72 @@ impl<'a> ThingOwned {
         Self::deserialize(&mut deserializer)
     }
 }
+
+/// Construct a [ThingOwned] with JSON-like syntax.
+///
+/// Values that aren't objects, arrays or `null` can be any expression that converts
+/// into a [ThingOwned]. Keys can be string literals or parenthesized expressions.
+///
+/// ```
+/// # use kserde::thing;
+/// let name = "kserde";
+/// let value = thing!({
+///     "name": name,
+///     "version": [0, 1],
+///     "license": null,
+///     (name.to_uppercase()): { "fast": true }
+/// });
+/// assert_eq!(value["KSERDE"]["fast"].bool(), Some(true));
+/// ```
+#[macro_export]
+macro_rules! thing {
+    (null) => {
+        $crate::ThingOwned::Null
+    };
+    ([ $($tt: tt)* ]) => {
+        $crate::ThingOwned::Array($crate::thing_internal!(@array [] () $($tt)*))
+    };
+    ({ $($tt: tt)* }) => {{
+        #[allow(unused_mut)]
+        let mut object = $crate::OrderedMap::new();
+        $crate::thing_internal!(@object object $($tt)*);
+        $crate::ThingOwned::Object(object)
+    }};
+    ($other: expr) => {
+        $crate::ThingOwned::from($other)
+    };
+}
+
+/// Token munching for [thing!]: each array element or property value is every token up to
+/// the next top-level comma.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! thing_internal {
+    (@array [$($elements: expr,)*] ()) => {
+        vec![$($elements,)*]
+    };
+    (@array [$($elements: expr,)*] ($($value: tt)+)) => {
+        vec![$($elements,)* $crate::thing!($($value)+)]
+    };
+    (@array [$($elements: expr,)*] ($($value: tt)+) , $($rest: tt)*) => {
+        $crate::thing_internal!(@array [$($elements,)* $crate::thing!($($value)+),] () $($rest)*)
+    };
+    (@array [$($elements: expr,)*] ($($value: tt)*) $next: tt $($rest: tt)*) => {
+        $crate::thing_internal!(@array [$($elements,)*] ($($value)* $next) $($rest)*)
+    };
+
+    (@object $object: ident) => {};
+    (@object $object: ident $key: tt : $($rest: tt)*) => {
+        $crate::thing_internal!(@value $object [$key] () $($rest)*)
+    };
+    (@value $object: ident [$key: tt] ($($value: tt)+)) => {
+        $object.insert(::std::string::String::from($key), $crate::thing!($($value)+));
+    };
+    (@value $object: ident [$key: tt] ($($value: tt)+) , $($rest: tt)*) => {
+        $object.insert(::std::string::String::from($key), $crate::thing!($($value)+));
+        $crate::thing_internal!(@object $object $($rest)*)
+    };
+    (@value $object: ident [$key: tt] ($($value: tt)*) $next: tt $($rest: tt)*) => {
+        $crate::thing_internal!(@value $object [$key] ($($value)* $next) $($rest)*)
+    };
+}
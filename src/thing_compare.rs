@@ -0,0 +1,277 @@
+use crate::{Thing, ThingNode, ThingOwned, ThingView};
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
+
+/// How numbers are compared by `eq_with` on [Thing] and [ThingOwned].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FloatComparison {
+    /// Numbers must be exactly equal. This is what `==` uses.
+    Exact,
+    /// Numbers may differ by this many units in the last place.
+    Ulps(u64),
+    /// Numbers may differ by at most this much.
+    Epsilon(f64),
+}
+
+/// 2^127, the first `f64` above the range of `i128`.
+const I128_END: f64 = 170_141_183_460_469_231_731_687_303_715_884_105_728.0;
+/// 2^128, the first `f64` above the range of `u128`.
+const U128_END: f64 = 340_282_366_920_938_463_463_374_607_431_768_211_456.0;
+
+/// An exact integer from either integer variant. `Unsigned` is only used above
+/// `i128::MAX`, so the derived ordering is numeric.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Integer {
+    Signed(i128),
+    Unsigned(u128),
+}
+
+impl Integer {
+    fn from_view<N: ThingNode>(view: &ThingView<'_, N>) -> Option<Self> {
+        match *view {
+            ThingView::Integer(i) => Some(Integer::Signed(i)),
+            ThingView::UInteger(u) => Some(Integer::from_unsigned(u)),
+            _ => None,
+        }
+    }
+
+    fn from_unsigned(u: u128) -> Self {
+        i128::try_from(u).map_or(Integer::Unsigned(u), Integer::Signed)
+    }
+
+    /// Converts a float with no fractional part in the range `-2^127..2^128`.
+    fn from_whole_float(f: f64) -> Self {
+        if f >= I128_END {
+            Integer::Unsigned(f as u128)
+        } else {
+            Integer::Signed(f as i128)
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        match self {
+            Integer::Signed(i) => i as f64,
+            Integer::Unsigned(u) => u as f64,
+        }
+    }
+}
+
+/// The order of different kinds of values in the canonical ordering.
+fn rank<N: ThingNode>(view: &ThingView<'_, N>) -> u8 {
+    match view {
+        ThingView::Null => 0,
+        ThingView::Bool(_) => 1,
+        ThingView::Integer(_) | ThingView::UInteger(_) | ThingView::Number(_) => 2,
+        ThingView::String(_) => 3,
+        ThingView::Bytes(_) => 4,
+        ThingView::Array(_) => 5,
+        ThingView::Object(_) => 6,
+    }
+}
+
+/// Orders floats so that `NaN` equals itself and is greater than everything else,
+/// and `-0.0` equals `0.0`.
+fn compare_floats(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b)
+        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
+
+/// Compares an integer and a float exactly, without rounding the integer to a float.
+fn compare_integer_float(i: Integer, f: f64) -> Ordering {
+    if f.is_nan() || f >= U128_END {
+        Ordering::Less
+    } else if f < -I128_END {
+        Ordering::Greater
+    } else {
+        let whole = f.trunc();
+        i.cmp(&Integer::from_whole_float(whole))
+            .then_with(|| compare_floats(0.0, f - whole))
+    }
+}
+
+fn compare_numbers<N: ThingNode>(a: &ThingView<'_, N>, b: &ThingView<'_, N>) -> Ordering {
+    match (Integer::from_view(a), Integer::from_view(b), a, b) {
+        (Some(a), Some(b), _, _) => a.cmp(&b),
+        (Some(a), None, _, ThingView::Number(b)) => compare_integer_float(a, *b),
+        (None, Some(b), ThingView::Number(a), _) => compare_integer_float(b, *a).reverse(),
+        (_, _, ThingView::Number(a), ThingView::Number(b)) => compare_floats(*a, *b),
+        _ => unreachable!(),
+    }
+}
+
+fn float_eq(a: f64, b: f64, comparison: FloatComparison) -> bool {
+    match comparison {
+        FloatComparison::Exact => compare_floats(a, b) == Ordering::Equal,
+        FloatComparison::Ulps(ulps) => {
+            if a == b {
+                true
+            } else if a.is_nan() || b.is_nan() || a.is_sign_negative() != b.is_sign_negative() {
+                false
+            } else {
+                (a.to_bits() as i64 - b.to_bits() as i64).unsigned_abs() <= ulps
+            }
+        }
+        FloatComparison::Epsilon(epsilon) => a == b || (a - b).abs() <= epsilon,
+    }
+}
+
+/// Properties sorted by key, so objects can be compared regardless of insertion order.
+fn sorted_properties<N: ThingNode>(properties: &[(N::Key, N)]) -> Vec<(&str, &N)> {
+    let mut properties: Vec<_> = properties.iter().map(|(k, v)| (k.as_ref(), v)).collect();
+    properties.sort_unstable_by_key(|(k, _)| *k);
+    properties
+}
+
+pub(crate) fn compare<N: ThingNode>(a: &N, b: &N) -> Ordering {
+    let (a, b) = (a.view(), b.view());
+    match (&a, &b) {
+        (ThingView::Bool(a), ThingView::Bool(b)) => a.cmp(b),
+        (ThingView::String(a), ThingView::String(b)) => a.cmp(b),
+        (ThingView::Bytes(a), ThingView::Bytes(b)) => a.cmp(b),
+        (ThingView::Array(a), ThingView::Array(b)) => a
+            .iter()
+            .zip(b.iter())
+            .map(|(a, b)| compare(a, b))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or_else(|| a.len().cmp(&b.len())),
+        (ThingView::Object(a), ThingView::Object(b)) => {
+            let (a, b) = (sorted_properties(a), sorted_properties(b));
+            a.iter()
+                .zip(b.iter())
+                .map(|((ak, av), (bk, bv))| ak.cmp(bk).then_with(|| compare(*av, *bv)))
+                .find(|o| *o != Ordering::Equal)
+                .unwrap_or_else(|| a.len().cmp(&b.len()))
+        }
+        _ if rank(&a) == 2 && rank(&b) == 2 => compare_numbers(&a, &b),
+        (ThingView::Null, ThingView::Null) => Ordering::Equal,
+        _ => rank(&a).cmp(&rank(&b)),
+    }
+}
+
+fn numbers_eq<N: ThingNode>(
+    a: &ThingView<'_, N>,
+    b: &ThingView<'_, N>,
+    comparison: FloatComparison,
+) -> bool {
+    match (Integer::from_view(a), Integer::from_view(b), a, b) {
+        (Some(a), Some(b), _, _) => a == b,
+        (Some(i), None, _, ThingView::Number(f)) | (None, Some(i), ThingView::Number(f), _) => {
+            match comparison {
+                FloatComparison::Exact => compare_integer_float(i, *f) == Ordering::Equal,
+                _ => float_eq(i.to_f64(), *f, comparison),
+            }
+        }
+        (_, _, ThingView::Number(a), ThingView::Number(b)) => float_eq(*a, *b, comparison),
+        _ => unreachable!(),
+    }
+}
+
+pub(crate) fn eq_with<N: ThingNode>(a: &N, b: &N, comparison: FloatComparison) -> bool {
+    let (a_view, b_view) = (a.view(), b.view());
+    match (&a_view, &b_view) {
+        _ if rank(&a_view) == 2 && rank(&b_view) == 2 => numbers_eq(&a_view, &b_view, comparison),
+        (ThingView::Array(a_items), ThingView::Array(b_items)) => {
+            a_items.len() == b_items.len()
+                && a_items
+                    .iter()
+                    .zip(b_items.iter())
+                    .all(|(a, b)| eq_with(a, b, comparison))
+        }
+        (ThingView::Object(a_properties), ThingView::Object(b_properties)) => {
+            a_properties.len() == b_properties.len()
+                && a_properties.iter().all(|(key, a)| {
+                    b.property(key.as_ref())
+                        .is_some_and(|b| eq_with(a, b, comparison))
+                })
+        }
+        (ThingView::Bool(a), ThingView::Bool(b)) => a == b,
+        (ThingView::String(a), ThingView::String(b)) => a == b,
+        (ThingView::Bytes(a), ThingView::Bytes(b)) => a == b,
+        (ThingView::Null, ThingView::Null) => true,
+        _ => false,
+    }
+}
+
+pub(crate) fn hash<N: ThingNode, H: Hasher>(value: &N, state: &mut H) {
+    let view = value.view();
+    rank(&view).hash(state);
+    match view {
+        ThingView::Bool(b) => b.hash(state),
+        ThingView::Integer(i) => Integer::Signed(i).hash(state),
+        ThingView::UInteger(u) => Integer::from_unsigned(u).hash(state),
+        // Numbers that equal an integer must hash like that integer.
+        ThingView::Number(f) if f.fract() == 0.0 && (-I128_END..U128_END).contains(&f) => {
+            Integer::from_whole_float(f).hash(state)
+        }
+        ThingView::Number(f) if f.is_nan() => u64::MAX.hash(state),
+        ThingView::Number(f) => f.to_bits().hash(state),
+        ThingView::String(s) => s.hash(state),
+        ThingView::Bytes(b) => b.hash(state),
+        ThingView::Array(a) => {
+            a.len().hash(state);
+            for value in a {
+                hash(value, state);
+            }
+        }
+        ThingView::Object(o) => {
+            // Combine property hashes with an operation that ignores order.
+            let mut combined = 0u64;
+            for (key, value) in o {
+                let mut hasher = DefaultHasher::new();
+                key.as_ref().hash(&mut hasher);
+                hash(value, &mut hasher);
+                combined = combined.wrapping_add(hasher.finish());
+            }
+            o.len().hash(state);
+            combined.hash(state);
+        }
+        ThingView::Null => {}
+    }
+}
+
+/// Structural comparisons for [Thing] and [ThingOwned].
+///
+/// Objects are equal if they have the same properties in any order. Integers and numbers
+/// are equal if they have the same value. For the total ordering `NaN` equals itself and
+/// sorts after every other number.
+macro_rules! thing_compare {
+    (($($generics: tt)*) $thing: ty) => {
+        impl $($generics)* $thing {
+            /// Compare with a configurable tolerance for numbers.
+            pub fn eq_with(&self, other: &Self, comparison: FloatComparison) -> bool {
+                eq_with(self, other, comparison)
+            }
+        }
+
+        impl $($generics)* PartialEq for $thing {
+            fn eq(&self, other: &Self) -> bool {
+                eq_with(self, other, FloatComparison::Exact)
+            }
+        }
+
+        impl $($generics)* Eq for $thing {}
+
+        impl $($generics)* PartialOrd for $thing {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl $($generics)* Ord for $thing {
+            fn cmp(&self, other: &Self) -> Ordering {
+                compare(self, other)
+            }
+        }
+
+        impl $($generics)* Hash for $thing {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                hash(self, state)
+            }
+        }
+    };
+}
+
+thing_compare!((<'a>) Thing<'a>);
+thing_compare!(() ThingOwned);
@@ -0,0 +1,265 @@
+use crate::{AnyValue, BytesEncoding, Deserialize, Deserializer, Thing, ThingOwned};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::slice::Iter;
+
+/// A borrowed view of a [Thing] or [ThingOwned] node.
+pub enum ThingView<'a, N: ThingNode> {
+    String(&'a str),
+    Bool(bool),
+    Integer(i128),
+    UInteger(u128),
+    Number(f64),
+    Bytes(&'a [u8]),
+    Object(&'a [(N::Key, N)]),
+    Array(&'a [N]),
+    Null,
+}
+
+/// Implemented by [Thing] and [ThingOwned] so [ThingDeserializer] can walk either.
+pub trait ThingNode: Sized {
+    type Key: AsRef<str>;
+    fn view(&self) -> ThingView<'_, Self>;
+    /// Look up a property if this is an object.
+    fn property(&self, key: &str) -> Option<&Self>;
+}
+
+impl<'a> ThingNode for Thing<'a> {
+    type Key = Cow<'a, str>;
+    fn view(&self) -> ThingView<'_, Self> {
+        match self {
+            Thing::String(s) => ThingView::String(s),
+            Thing::Bool(b) => ThingView::Bool(*b),
+            Thing::Integer(i) => ThingView::Integer(*i),
+            Thing::UInteger(u) => ThingView::UInteger(*u),
+            Thing::Number(n) => ThingView::Number(*n),
+            Thing::Bytes(b) => ThingView::Bytes(b),
+            Thing::Object(o) => ThingView::Object(o.as_slice()),
+            Thing::Array(a) => ThingView::Array(a),
+            Thing::Null => ThingView::Null,
+        }
+    }
+
+    fn property(&self, key: &str) -> Option<&Self> {
+        self.get(key)
+    }
+}
+
+impl ThingNode for ThingOwned {
+    type Key = String;
+    fn view(&self) -> ThingView<'_, Self> {
+        match self {
+            ThingOwned::String(s) => ThingView::String(s),
+            ThingOwned::Bool(b) => ThingView::Bool(*b),
+            ThingOwned::Integer(i) => ThingView::Integer(*i),
+            ThingOwned::UInteger(u) => ThingView::UInteger(*u),
+            ThingOwned::Number(n) => ThingView::Number(*n),
+            ThingOwned::Bytes(b) => ThingView::Bytes(b),
+            ThingOwned::Object(o) => ThingView::Object(o.as_slice()),
+            ThingOwned::Array(a) => ThingView::Array(a),
+            ThingOwned::Null => ThingView::Null,
+        }
+    }
+
+    fn property(&self, key: &str) -> Option<&Self> {
+        self.get(key)
+    }
+}
+
+enum Frame<'a, N: ThingNode> {
+    Object(Iter<'a, (N::Key, N)>),
+    Array(Iter<'a, N>),
+}
+
+/// Deserializes values directly from a [Thing] or [ThingOwned] instead of parsing text.
+///
+/// Strings and bytes are borrowed from the tree. Integers may also be read from numbers
+/// with no fractional part and bytes may also be read from base64 strings.
+pub struct ThingDeserializer<'a, N: ThingNode, CONTEXT> {
+    /// The value that will be read next.
+    next: Option<&'a N>,
+    /// The objects and arrays that are being walked.
+    stack: Vec<Frame<'a, N>>,
+    context: CONTEXT,
+}
+
+impl<'a, N: ThingNode> ThingDeserializer<'a, N, ()> {
+    pub fn new(thing: &'a N) -> Self {
+        Self {
+            next: Some(thing),
+            stack: Vec::new(),
+            context: (),
+        }
+    }
+}
+
+impl<'a, N: ThingNode, CONTEXT> ThingDeserializer<'a, N, CONTEXT> {
+    fn take(&mut self) -> Option<ThingView<'a, N>> {
+        self.next.take().map(|n| n.view())
+    }
+
+    fn integer<T: TryFrom<i128> + TryFrom<u128>>(&mut self) -> Option<T> {
+        match self.take()? {
+            ThingView::Integer(i) => T::try_from(i).ok(),
+            ThingView::UInteger(u) => T::try_from(u).ok(),
+            // `as` saturates, so numbers outside the range of `i128` must be rejected first.
+            ThingView::Number(n)
+                if n.fract() == 0.0 && n >= i128::MIN as f64 && n < -(i128::MIN as f64) =>
+            {
+                T::try_from(n as i128).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'a, N: ThingNode, CONTEXT> Deserializer<'a> for ThingDeserializer<'a, N, CONTEXT> {
+    type Context = CONTEXT;
+
+    fn string(&mut self) -> Option<Cow<'a, str>> {
+        match self.take()? {
+            ThingView::String(s) => Some(Cow::Borrowed(s)),
+            _ => None,
+        }
+    }
+
+    fn bool(&mut self) -> Option<bool> {
+        match self.take()? {
+            ThingView::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    fn i64(&mut self) -> Option<i64> {
+        self.integer()
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.integer()
+    }
+
+    fn i128(&mut self) -> Option<i128> {
+        self.integer()
+    }
+
+    fn u128(&mut self) -> Option<u128> {
+        match self.take()? {
+            ThingView::Integer(i) => u128::try_from(i).ok(),
+            ThingView::UInteger(u) => Some(u),
+            ThingView::Number(n) if n.fract() == 0.0 && n >= 0.0 && n < u128::MAX as f64 => {
+                Some(n as u128)
+            }
+            _ => None,
+        }
+    }
+
+    fn f64(&mut self) -> Option<f64> {
+        match self.take()? {
+            ThingView::Number(n) => Some(n),
+            ThingView::Integer(i) => Some(i as f64),
+            ThingView::UInteger(u) => Some(u as f64),
+            _ => None,
+        }
+    }
+
+    fn bytes(&mut self) -> Option<Cow<'a, [u8]>> {
+        match self.take()? {
+            ThingView::Bytes(b) => Some(Cow::Borrowed(b)),
+            ThingView::String(s) => BytesEncoding::default().decode(s).map(Cow::Owned),
+            _ => None,
+        }
+    }
+
+    fn null(&mut self) -> bool {
+        match self.next.map(|n| n.view()) {
+            Some(ThingView::Null) => {
+                self.next = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn any<'b>(&'b mut self) -> Option<AnyValue<'a>> {
+        Some(match self.take()? {
+            ThingView::String(s) => AnyValue::String(Cow::Borrowed(s)),
+            ThingView::Bool(b) => AnyValue::Bool(b),
+            ThingView::Integer(i) => AnyValue::Integer(i),
+            ThingView::UInteger(u) => AnyValue::UInteger(u),
+            ThingView::Number(n) => AnyValue::Number(n),
+            ThingView::Bytes(b) => AnyValue::Bytes(Cow::Borrowed(b)),
+            ThingView::Object(o) => {
+                self.stack.push(Frame::Object(o.iter()));
+                AnyValue::Object
+            }
+            ThingView::Array(a) => {
+                self.stack.push(Frame::Array(a.iter()));
+                AnyValue::Array
+            }
+            ThingView::Null => AnyValue::Null,
+        })
+    }
+
+    fn begin_object(&mut self) -> bool {
+        match self.take() {
+            Some(ThingView::Object(o)) => {
+                self.stack.push(Frame::Object(o.iter()));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn has_property(&mut self) -> Option<Cow<'a, str>> {
+        match self.stack.last_mut() {
+            Some(Frame::Object(iter)) => match iter.next() {
+                Some((key, value)) => {
+                    self.next = Some(value);
+                    Some(Cow::Borrowed(key.as_ref()))
+                }
+                None => {
+                    self.stack.pop();
+                    None
+                }
+            },
+            _ => None,
+        }
+    }
+
+    fn begin_array(&mut self) -> bool {
+        match self.take() {
+            Some(ThingView::Array(a)) => {
+                self.stack.push(Frame::Array(a.iter()));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn has_array_value(&mut self) -> bool {
+        match self.stack.last_mut() {
+            Some(Frame::Array(iter)) => match iter.next() {
+                Some(value) => {
+                    self.next = Some(value);
+                    true
+                }
+                None => {
+                    self.stack.pop();
+                    false
+                }
+            },
+            _ => false,
+        }
+    }
+
+    fn get_context_mut(&mut self) -> &mut Self::Context {
+        &mut self.context
+    }
+}
+
+/// Convert a [Thing] or [ThingOwned] to a value without going through text.
+pub fn from_thing<'a, T: Deserialize<'a, ThingDeserializer<'a, N, ()>>, N: ThingNode>(
+    thing: &'a N,
+) -> Option<T> {
+    T::deserialize(&mut ThingDeserializer::new(thing))
+}
@@ -0,0 +1,130 @@
+use crate::{OrderedMap, Serialize, Serializer, ThingOwned};
+
+/// Builds a [ThingOwned] directly instead of writing a text format.
+pub struct ThingSerializer<CONTEXT> {
+    /// Values that are being built. Objects and arrays stay here until they're finished.
+    stack: Vec<ThingOwned>,
+    context: CONTEXT,
+}
+
+impl ThingSerializer<()> {
+    pub fn new() -> Self {
+        Self::new_with_context(())
+    }
+}
+
+impl Default for ThingSerializer<()> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<CONTEXT> ThingSerializer<CONTEXT> {
+    fn new_with_context(context: CONTEXT) -> Self {
+        Self {
+            stack: Vec::new(),
+            context,
+        }
+    }
+
+    fn push(&mut self, value: ThingOwned) {
+        self.stack.push(value);
+    }
+
+    /// Serialize a value and take the result off the stack.
+    fn build<V: Serialize<Self> + ?Sized>(&mut self, value: &V) -> ThingOwned {
+        let depth = self.stack.len();
+        value.serialize(self);
+        if self.stack.len() > depth {
+            self.stack.pop().unwrap()
+        } else {
+            ThingOwned::Null
+        }
+    }
+}
+
+impl<CONTEXT> Serializer for ThingSerializer<CONTEXT> {
+    type Context = CONTEXT;
+    type Result = ThingOwned;
+
+    fn string(&mut self, s: &str) {
+        self.push(ThingOwned::String(s.to_string()))
+    }
+
+    fn bool(&mut self, b: bool) {
+        self.push(ThingOwned::Bool(b))
+    }
+
+    fn i64(&mut self, i: i64) {
+        self.push(ThingOwned::Integer(i.into()))
+    }
+
+    fn u64(&mut self, u: u64) {
+        self.push(ThingOwned::Integer(u.into()))
+    }
+
+    fn i128(&mut self, i: i128) {
+        self.push(ThingOwned::Integer(i))
+    }
+
+    fn u128(&mut self, u: u128) {
+        self.push(u.into())
+    }
+
+    fn f64(&mut self, n: f64) {
+        self.push(ThingOwned::Number(n))
+    }
+
+    fn null(&mut self) {
+        self.push(ThingOwned::Null)
+    }
+
+    fn bytes(&mut self, b: &[u8]) {
+        self.push(ThingOwned::Bytes(b.to_vec()))
+    }
+
+    fn done(mut self) -> Self::Result {
+        self.stack.pop().unwrap_or(ThingOwned::Null)
+    }
+
+    fn begin_object(&mut self) {
+        self.push(ThingOwned::Object(OrderedMap::new()))
+    }
+
+    fn end_object(&mut self) {}
+
+    fn property<V: Serialize<Self>>(&mut self, name: &str, value: &V) {
+        let value = self.build(value);
+        if let Some(ThingOwned::Object(o)) = self.stack.last_mut() {
+            o.insert(name.to_string(), value);
+        }
+    }
+
+    fn begin_array(&mut self) {
+        self.push(ThingOwned::Array(Vec::new()))
+    }
+
+    fn end_array(&mut self) {}
+
+    fn value<V: Serialize<Self>>(&mut self, value: &V) {
+        let value = self.build(value);
+        if let Some(ThingOwned::Array(a)) = self.stack.last_mut() {
+            a.push(value);
+        }
+    }
+
+    fn get_context(&self) -> &Self::Context {
+        &self.context
+    }
+
+    fn get_context_mut(&mut self) -> &mut Self::Context {
+        &mut self.context
+    }
+}
+
+/// Convert a value to a [ThingOwned] without going through text.
+pub fn to_thing<T: Serialize<ThingSerializer<()>> + ?Sized>(value: &T) -> ThingOwned {
+    let mut serializer = ThingSerializer::new();
+    value.serialize(&mut serializer);
+    serializer.done()
+}
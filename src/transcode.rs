@@ -0,0 +1,69 @@
+use crate::{AnyValue, Deserializer, Serialize, Serializer};
+use std::cell::{Cell, RefCell};
+
+/// Stream a value from a [Deserializer] straight into a [Serializer] without building
+/// an intermediate tree.
+///
+/// Returns `None` if the deserializer fails. The serializer may have already received
+/// part of the value by then.
+pub fn transcode<'a, D: Deserializer<'a>, S: Serializer>(
+    deserializer: &mut D,
+    serializer: &mut S,
+) -> Option<()> {
+    let transcoder = Transcoder {
+        deserializer: RefCell::new(deserializer),
+        failed: Cell::new(false),
+    };
+    transcoder.serialize(serializer);
+    (!transcoder.failed.get()).then_some(())
+}
+
+/// Serializing a `Transcoder` reads the next value from the deserializer.
+/// The `RefCell` is needed because `Serialize` only gets `&self`.
+struct Transcoder<'d, D> {
+    deserializer: RefCell<&'d mut D>,
+    failed: Cell<bool>,
+}
+
+impl<'a, 'd, D: Deserializer<'a>, S: Serializer> Serialize<S> for Transcoder<'d, D> {
+    fn serialize(&self, serializer: &mut S) {
+        if self.failed.get() {
+            return;
+        }
+        let value = self.deserializer.borrow_mut().any();
+        match value {
+            Some(AnyValue::Object) => {
+                serializer.begin_object();
+                // The borrow must end before the property's value is transcoded.
+                while let Some(name) = {
+                    let name = self.deserializer.borrow_mut().has_property();
+                    name
+                } {
+                    serializer.property(&name, self);
+                    if self.failed.get() {
+                        return;
+                    }
+                }
+                serializer.end_object();
+            }
+            Some(AnyValue::Array) => {
+                serializer.begin_array();
+                while self.deserializer.borrow_mut().has_array_value() {
+                    serializer.value(self);
+                    if self.failed.get() {
+                        return;
+                    }
+                }
+                serializer.end_array();
+            }
+            Some(AnyValue::String(s)) => serializer.string(&s),
+            Some(AnyValue::Bool(b)) => serializer.bool(b),
+            Some(AnyValue::Integer(i)) => serializer.i128(i),
+            Some(AnyValue::UInteger(u)) => serializer.u128(u),
+            Some(AnyValue::Number(n)) => serializer.f64(n),
+            Some(AnyValue::Bytes(b)) => serializer.bytes(&b),
+            Some(AnyValue::Null) => serializer.null(),
+            None => self.failed.set(true),
+        }
+    }
+}
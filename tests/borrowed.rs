@@ -0,0 +1,30 @@
+use kserde::*;
+use std::borrow::Cow;
+
+#[test]
+fn borrowed_str() {
+    let source = String::from("[\"hello\", \"world\"]");
+    let words: Vec<&str> = Vec::from_json(&source).unwrap();
+    assert_eq!(words, ["hello", "world"]);
+    assert_eq!(words[0].as_ptr(), source[2..].as_ptr());
+}
+
+#[test]
+fn borrowed_str_with_escapes_fails() {
+    let mut deserializer = JSONDeserializer::new("\"line\\nbreak\"");
+    assert_eq!(<&str>::deserialize(&mut deserializer), None);
+    assert_eq!(deserializer.error(), Some(&DeserializeError::CannotBorrow));
+
+    // Owned types still work.
+    assert_eq!(
+        Cow::<str>::from_json("\"line\\nbreak\""),
+        Some(Cow::Owned("line\nbreak".to_string()))
+    );
+}
+
+#[test]
+fn borrowed_bytes_cannot_come_from_json() {
+    let mut deserializer = JSONDeserializer::new("\"AQID\"");
+    assert_eq!(<&[u8]>::deserialize(&mut deserializer), None);
+    assert_eq!(deserializer.error(), Some(&DeserializeError::CannotBorrow));
+}
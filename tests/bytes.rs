@@ -0,0 +1,47 @@
+use kserde::*;
+
+#[test]
+fn bytes_as_base64() {
+    let bytes = Bytes(vec![0u8, 1, 2, 253, 254, 255]);
+    let json = bytes.to_json();
+    assert_eq!(json, "\"AAEC/f7/\"");
+    assert_eq!(Bytes::from_json(&json), Some(bytes));
+
+    assert_eq!(Bytes(b"f".to_vec()).to_json(), "\"Zg==\"");
+    assert_eq!(Bytes(b"fo".to_vec()).to_json(), "\"Zm8=\"");
+    assert_eq!(
+        Bytes::<Vec<u8>>::from_json("\"Zm8\""),
+        Some(Bytes(b"fo".to_vec()))
+    );
+    assert_eq!(Bytes::<Vec<u8>>::from_json("\"Z\""), None);
+    assert_eq!(Bytes::<Vec<u8>>::from_json("\"Z!==\""), None);
+}
+
+#[test]
+fn configurable_encoding() {
+    let bytes = Bytes(vec![0xfbu8, 0xff]);
+
+    for (encoding, expected) in [
+        (BytesEncoding::Base64, "\"+/8=\""),
+        (BytesEncoding::Base64Url, "\"-_8\""),
+        (BytesEncoding::Hex, "\"fbff\""),
+    ] {
+        let mut serializer = JSONSerializer::new().with_bytes_encoding(encoding);
+        bytes.serialize(&mut serializer);
+        let json = serializer.done();
+        assert_eq!(json, expected);
+
+        let mut deserializer = JSONDeserializer::new(&json).with_bytes_encoding(encoding);
+        assert_eq!(Bytes::deserialize(&mut deserializer), Some(bytes.clone()));
+    }
+
+    let mut deserializer = JSONDeserializer::new("\"abc\"").with_bytes_encoding(BytesEncoding::Hex);
+    assert_eq!(Bytes::<Vec<u8>>::deserialize(&mut deserializer), None);
+}
+
+#[test]
+fn thing_bytes() {
+    let thing = Thing::Bytes(vec![1, 2, 3].into());
+    assert_eq!(thing.to_json(), "\"AQID\"");
+    assert_eq!(thing.to_owned().to_json(), "\"AQID\"");
+}
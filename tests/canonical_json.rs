@@ -0,0 +1,80 @@
+use kserde::*;
+use std::collections::HashMap;
+
+#[test]
+fn rfc_8785_example() {
+    let source = r#"{
+        "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+        "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+        "literals": [null, true, false]
+    }"#;
+    let thing = ThingOwned::from_json(source).unwrap();
+    assert_eq!(
+        thing.to_canonical_json(),
+        r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+    );
+}
+
+#[test]
+fn keys_sort_by_utf16() {
+    let source = r#"{"\u20ac": 1, "\r": 2, "\ufb33": 3, "1": 4, "\ud83d\ude00": 5, "\u0080": 6, "\u00f6": 7}"#;
+    let thing = ThingOwned::from_json(source).unwrap();
+    assert_eq!(
+        thing.to_canonical_json(),
+        "{\"\\r\":2,\"1\":4,\"\u{80}\":6,\"ö\":7,\"€\":1,\"😀\":5,\"\u{fb33}\":3}"
+    );
+}
+
+#[test]
+fn ecmascript_numbers() {
+    let cases: &[(f64, &str)] = &[
+        (0.0, "0"),
+        (-0.0, "0"),
+        (1.0, "1"),
+        (-1.5, "-1.5"),
+        (1e20, "100000000000000000000"),
+        (1e21, "1e+21"),
+        (123e-20, "1.23e-18"),
+        (0.000001, "0.000001"),
+        (0.0000001, "1e-7"),
+        (9007199254740993.0, "9007199254740992"),
+        (5e-324, "5e-324"),
+        (1.7976931348623157e308, "1.7976931348623157e+308"),
+        (f64::NAN, "null"),
+    ];
+    for (n, expected) in cases {
+        assert_eq!(n.to_canonical_json(), *expected);
+    }
+    assert_eq!(
+        12345678901234567890u64.to_canonical_json(),
+        "12345678901234567000"
+    );
+}
+
+#[test]
+fn equal_inputs_give_identical_bytes() {
+    let a: HashMap<String, Vec<i32>> = (0..20).map(|i| (i.to_string(), vec![i, -i])).collect();
+    let b: HashMap<String, Vec<i32>> = (0..20)
+        .rev()
+        .map(|i| (i.to_string(), vec![i, -i]))
+        .collect();
+    assert_eq!(a.to_canonical_json(), b.to_canonical_json());
+    assert_eq!(
+        thing!({"b": [], "a": {}, "c": [[], 1]}).to_canonical_json(),
+        r#"{"a":{},"b":[],"c":[[],1]}"#
+    );
+}
+
+#[test]
+fn nested_objects_are_sorted() {
+    let source = r#"{"z": [{"y": 1, "x": {"b": {}, "a": [{"d": 2, "c": 3}]}}, {}], "a": {"z": {}, "y": "s"}}"#;
+    assert_eq!(
+        ThingOwned::from_json(source).unwrap().to_canonical_json(),
+        r#"{"a":{"y":"s","z":{}},"z":[{"x":{"a":[{"c":3,"d":2}],"b":{}},"y":1},{}]}"#
+    );
+    let source = r#"[{"b": 1, "a": 2}, {"d": {}, "c": 0}]"#;
+    assert_eq!(
+        ThingOwned::from_json(source).unwrap().to_canonical_json(),
+        r#"[{"a":2,"b":1},{"c":0,"d":{}}]"#
+    );
+}
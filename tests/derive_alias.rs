@@ -0,0 +1,89 @@
+#![cfg(feature = "kserde_derive")]
+use kserde::*;
+
+#[derive(SerializeDeserialize, Debug, PartialEq, Default)]
+struct Save {
+    #[alias = "hp"]
+    #[alias = "hit_points"]
+    health: u32,
+    #[skip_serializing_if = "Option::is_none"]
+    nickname: Option<String>,
+    #[skip_serializing_if = "Vec::is_empty"]
+    items: Vec<String>,
+}
+
+#[test]
+fn aliases() {
+    let expected = Some(Save {
+        health: 5,
+        ..Default::default()
+    });
+    assert_eq!(Save::from_json(r#"{"health": 5, "items": []}"#), expected);
+    assert_eq!(Save::from_json(r#"{"hp": 5, "items": []}"#), expected);
+    assert_eq!(
+        Save::from_json(r#"{"hit_points": 5, "items": []}"#),
+        expected
+    );
+
+    let mut save = Save::default();
+    save.deserialize_in_place(&mut JSONDeserializer::new(r#"{"hp": 9}"#))
+        .unwrap();
+    assert_eq!(save.health, 9);
+}
+
+#[test]
+fn skip_serializing_if() {
+    let mut save = Save {
+        health: 1,
+        ..Default::default()
+    };
+    let thing = to_thing(&save);
+    assert!(thing.get("nickname").is_none() && thing.get("items").is_none());
+    assert_eq!(thing.get_i64("health"), Some(1));
+
+    save.nickname = Some("Pip".to_string());
+    save.items.push("sword".to_string());
+    let thing = to_thing(&save);
+    assert_eq!(thing["nickname"].as_str(), Some("Pip"));
+    assert_eq!(thing["items"][0].as_str(), Some("sword"));
+}
+
+#[test]
+fn duplicate_fields() {
+    let source = r#"{"health": 1, "items": [], "hp": 2}"#;
+    let read = |duplicate_keys| {
+        let mut deserializer = JSONDeserializer::new(source).with_duplicate_keys(duplicate_keys);
+        let save = Save::deserialize(&mut deserializer);
+        (save.map(|s| s.health), deserializer.error().cloned())
+    };
+    assert_eq!(read(DuplicateKeys::KeepLast), (Some(2), None));
+    assert_eq!(read(DuplicateKeys::KeepFirst), (Some(1), None));
+    assert_eq!(
+        read(DuplicateKeys::Error),
+        (
+            None,
+            Some(DeserializeError::DuplicateKey {
+                key: "health".to_string(),
+                first: Some(11),
+                second: Some(33),
+            })
+        )
+    );
+
+    let mut save = Save::default();
+    let mut deserializer =
+        JSONDeserializer::new(source).with_duplicate_keys(DuplicateKeys::KeepFirst);
+    save.deserialize_in_place(&mut deserializer).unwrap();
+    assert_eq!(save.health, 1);
+}
+
+#[test]
+fn unknown_properties_are_skipped() {
+    let source = r#"{"extra": [1], "health": 2, "more": {"a": [{}]}, "items": []}"#;
+    assert_eq!(Save::from_json(source).map(|s| s.health), Some(2));
+
+    let mut save = Save::default();
+    save.deserialize_in_place(&mut JSONDeserializer::new(source))
+        .unwrap();
+    assert_eq!(save.health, 2);
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "kserde_derive")]
+use kserde::*;
+
+#[derive(SerializeDeserialize, Debug, PartialEq)]
+struct Msg<'a> {
+    name: &'a str,
+    tags: Vec<&'a str>,
+    id: u32,
+}
+
+#[test]
+fn borrowed_fields() {
+    let source = String::from(r#"{"name": "ping", "tags": ["a", "b"], "id": 3}"#);
+    let msg = Msg::from_json(&source).unwrap();
+    assert_eq!(
+        msg,
+        Msg {
+            name: "ping",
+            tags: vec!["a", "b"],
+            id: 3,
+        }
+    );
+    assert_eq!(msg.name.as_ptr(), source[10..].as_ptr());
+    assert_eq!(Msg::from_json(&msg.to_json()), Some(msg));
+
+    // A string with escapes can't be borrowed.
+    assert_eq!(
+        Msg::from_json(r#"{"name": "p\"ng", "tags": [], "id": 3}"#),
+        None
+    );
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "kserde_derive")]
+use kserde::*;
+
+/// Stands in for something like a GPU handle that can't be deserialized.
+#[derive(Debug)]
+struct Handle(u32);
+
+impl<S: Serializer> Serialize<S> for Handle {
+    fn serialize(&self, serializer: &mut S) {
+        serializer.u64(self.0 as u64)
+    }
+}
+
+#[derive(Serialize)]
+struct Texture<'a> {
+    name: &'a str,
+    handle: Handle,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Request<'a> {
+    path: &'a str,
+    retries: u8,
+}
+
+#[test]
+fn serialize_only() {
+    let texture = Texture {
+        name: "grass",
+        handle: Handle(7),
+    };
+    assert_eq!(
+        texture.to_json(),
+        "{\n    \"name\": \"grass\",\n    \"handle\": 7\n}"
+    );
+}
+
+#[test]
+fn deserialize_only() {
+    let source = String::from("{\"path\": \"/index\", \"retries\": 3}");
+    assert_eq!(
+        Request::from_json(&source),
+        Some(Request {
+            path: "/index",
+            retries: 3
+        })
+    );
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "kserde_derive")]
+use kserde::*;
+use std::fmt::Debug;
+
+#[derive(SerializeDeserialize, Debug, PartialEq)]
+struct Wrapper<T> {
+    inner: T,
+    list: Vec<T>,
+    count: u32,
+}
+
+#[derive(SerializeDeserialize, Debug, PartialEq)]
+struct Constrained<T>
+where
+    T: Debug,
+{
+    value: T,
+}
+
+#[derive(SerializeDeserialize, Debug, PartialEq)]
+struct Pair<A, B>(A, B);
+
+// The field's type doesn't mention `T`, so no bound is added and `T` needs no impls.
+#[derive(SerializeDeserialize, Debug, PartialEq)]
+struct Tagged<T> {
+    name: String,
+    #[skip]
+    marker: std::marker::PhantomData<T>,
+}
+
+struct NotSerializable;
+
+#[derive(SerializeDeserialize, Debug, PartialEq)]
+#[serialize_bound = "T: kserde::Serialize<KSer>"]
+#[deserialize_bound = "T: kserde::Deserialize<'kserde, KDes>"]
+struct Explicit<T> {
+    value: Box<T>,
+}
+
+#[test]
+fn generic_struct_round_trips() {
+    let wrapper = Wrapper {
+        inner: "a".to_string(),
+        list: vec!["b".to_string()],
+        count: 2,
+    };
+    assert_eq!(Wrapper::from_json(&wrapper.to_json()), Some(wrapper));
+
+    let constrained = Constrained { value: 1.5 };
+    assert_eq!(
+        Constrained::from_json(&constrained.to_json()),
+        Some(constrained)
+    );
+
+    let pair = Pair(1u8, true);
+    assert_eq!(Pair::from_json(&pair.to_json()), Some(pair));
+
+    let tagged: Tagged<NotSerializable> = Tagged::from_json("{\"name\": \"x\"}").unwrap();
+    assert_eq!(tagged.to_json(), "{\n    \"name\": \"x\"\n}");
+
+    let explicit = Explicit {
+        value: Box::new(3i64),
+    };
+    assert_eq!(Explicit::from_json(&explicit.to_json()), Some(explicit));
+}
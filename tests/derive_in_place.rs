@@ -0,0 +1,46 @@
+#![cfg(feature = "kserde_derive")]
+use kserde::*;
+
+#[derive(SerializeDeserialize, Debug, PartialEq)]
+struct Config {
+    name: String,
+    values: Vec<u32>,
+    scale: f32,
+}
+
+#[test]
+fn derived_in_place() {
+    let mut config = Config {
+        name: String::with_capacity(64),
+        values: Vec::with_capacity(64),
+        scale: 2.0,
+    };
+    config.name.push_str("old");
+    let name = config.name.as_ptr();
+    let values = config.values.as_ptr();
+
+    config
+        .deserialize_in_place(&mut JSONDeserializer::new(
+            r#"{"name": "new", "values": [1, 2, 3]}"#,
+        ))
+        .unwrap();
+    assert_eq!(
+        config,
+        Config {
+            name: "new".to_string(),
+            values: vec![1, 2, 3],
+            scale: 2.0,
+        }
+    );
+    // Existing allocations are reused.
+    assert_eq!(config.name.as_ptr(), name);
+    assert_eq!(config.values.as_ptr(), values);
+
+    // Properties that aren't mentioned are left untouched.
+    config
+        .deserialize_in_place(&mut JSONDeserializer::new(r#"{"scale": 0.5}"#))
+        .unwrap();
+    assert_eq!(config.name, "new");
+    assert_eq!(config.values, [1, 2, 3]);
+    assert_eq!(config.scale, 0.5);
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "kserde_derive")]
+use kserde::*;
+
+type Maybe<T> = Option<T>;
+
+#[derive(SerializeDeserialize, Debug, PartialEq)]
+struct Settings {
+    name: String,
+    width: Option<u32>,
+    height: std::option::Option<u32>,
+    title: Maybe<String>,
+}
+
+#[derive(SerializeDeserialize, Debug, PartialEq)]
+struct Marker;
+
+#[test]
+fn option_fields() {
+    let settings = Settings::from_json(r#"{"name": "a", "height": 3, "title": null}"#).unwrap();
+    assert_eq!(
+        settings,
+        Settings {
+            name: "a".to_string(),
+            width: None,
+            height: Some(3),
+            title: None,
+        }
+    );
+    assert_eq!(Settings::from_json(&settings.to_json()), Some(settings));
+
+    // Only `Option` fields may be missing.
+    assert_eq!(Settings::from_json(r#"{"height": 3}"#), None);
+}
+
+#[test]
+fn unit_struct() {
+    assert_eq!(Marker::from_json("{}"), Some(Marker));
+    assert_eq!(Marker::from_json(&Marker.to_json()), Some(Marker));
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "kserde_derive")]
+use kserde::*;
+
+/// Doesn't implement `Default`.
+#[derive(Debug, PartialEq)]
+struct Seed(u64);
+
+fn default_seed() -> Seed {
+    Seed(42)
+}
+
+fn default_volume() -> f32 {
+    0.5
+}
+
+#[derive(SerializeDeserialize, Debug, PartialEq)]
+struct Config {
+    #[skip_serializing]
+    password: String,
+    #[skip_deserializing]
+    cached_len: usize,
+    #[skip]
+    #[default = "default_seed"]
+    seed: Seed,
+    #[default = "default_volume"]
+    volume: f32,
+}
+
+#[test]
+fn skip_directions() {
+    let config = Config {
+        password: "hunter2".to_string(),
+        cached_len: 7,
+        seed: Seed(1),
+        volume: 1.0,
+    };
+    let thing = to_thing(&config);
+    assert!(thing.get("password").is_none());
+    assert!(thing.get("seed").is_none());
+    assert_eq!(thing.get_u64("cached_len"), Some(7));
+
+    let read = Config::from_json(r#"{"password": "secret", "cached_len": 99}"#).unwrap();
+    assert_eq!(
+        read,
+        Config {
+            password: "secret".to_string(),
+            cached_len: 0,
+            seed: Seed(42),
+            volume: 0.5,
+        }
+    );
+
+    // A missing field without a default is still an error.
+    assert_eq!(Config::from_json(r#"{"cached_len": 99}"#), None);
+}
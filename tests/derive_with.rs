@@ -0,0 +1,101 @@
+#![cfg(feature = "kserde_derive")]
+use kserde::*;
+
+/// Stands in for a type from another crate that doesn't implement kserde's traits.
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Vec2 {
+    x: f32,
+    y: f32,
+}
+
+mod vec2_array {
+    use super::Vec2;
+    use kserde::*;
+
+    pub fn serialize<S: Serializer>(value: &Vec2, serializer: &mut S) {
+        [value.x, value.y].serialize(serializer)
+    }
+
+    pub fn deserialize<'a, D: Deserializer<'a>>(deserializer: &mut D) -> Option<Vec2> {
+        let [x, y] = <[f32; 2]>::deserialize(deserializer)?;
+        Some(Vec2 { x, y })
+    }
+}
+
+fn serialize_id<S: Serializer>(id: &u64, serializer: &mut S) {
+    serializer.string(&format!("id-{}", id))
+}
+
+fn deserialize_id<'a, D: Deserializer<'a>>(deserializer: &mut D) -> Option<u64> {
+    String::deserialize(deserializer)?
+        .strip_prefix("id-")?
+        .parse()
+        .ok()
+}
+
+#[derive(SerializeDeserialize, Debug, PartialEq)]
+struct Sprite {
+    #[with = "vec2_array"]
+    position: Vec2,
+    #[serialize_with = "serialize_id"]
+    #[deserialize_with = "deserialize_id"]
+    id: u64,
+}
+
+#[derive(SerializeDeserialize, Debug, PartialEq, Clone)]
+#[from = "String"]
+#[into = "String"]
+struct Name {
+    first: String,
+    last: String,
+}
+
+impl From<String> for Name {
+    fn from(s: String) -> Self {
+        let mut parts = s.splitn(2, ' ');
+        Name {
+            first: parts.next().unwrap_or("").to_string(),
+            last: parts.next().unwrap_or("").to_string(),
+        }
+    }
+}
+
+impl From<Name> for String {
+    fn from(name: Name) -> Self {
+        format!("{} {}", name.first, name.last)
+    }
+}
+
+#[test]
+fn with_functions() {
+    let sprite = Sprite {
+        position: Vec2 { x: 1.0, y: 2.5 },
+        id: 7,
+    };
+    let json = sprite.to_json();
+    assert_eq!(
+        Thing::from_json(&json).unwrap()["id"].as_str(),
+        Some("id-7")
+    );
+    assert_eq!(Sprite::from_json(&json), Some(sprite));
+
+    let mut sprite = Sprite::from_json(r#"{"position": [0, 0], "id": "id-1"}"#).unwrap();
+    sprite
+        .deserialize_in_place(&mut JSONDeserializer::new(r#"{"position": [3, 4]}"#))
+        .unwrap();
+    assert_eq!(sprite.position, Vec2 { x: 3.0, y: 4.0 });
+    assert_eq!(sprite.id, 1);
+
+    assert_eq!(Sprite::from_json(r#"{"position": [0, 0], "id": 1}"#), None);
+    assert_eq!(Sprite::from_json(r#"{"position": [0, 0]}"#), None);
+}
+
+#[test]
+fn from_and_into() {
+    let name = Name {
+        first: "Ada".to_string(),
+        last: "Lovelace".to_string(),
+    };
+    assert_eq!(name.to_json(), "\"Ada Lovelace\"");
+    assert_eq!(Name::from_json("\"Ada Lovelace\""), Some(name));
+}
@@ -0,0 +1,58 @@
+use kserde::*;
+use std::collections::{BTreeMap, HashMap};
+
+const SOURCE: &str = r#"{"x": 1, "y": 2, "x": 3}"#;
+
+fn deserialize<T: Deserialize<'static, JSONDeserializer<'static, ()>>>(
+    duplicate_keys: DuplicateKeys,
+) -> (Option<T>, Option<DeserializeError>) {
+    let mut deserializer = JSONDeserializer::new(SOURCE).with_duplicate_keys(duplicate_keys);
+    let value = T::deserialize(&mut deserializer);
+    (value, deserializer.error().cloned())
+}
+
+#[test]
+fn maps() {
+    let (map, _) = deserialize::<HashMap<String, i32>>(DuplicateKeys::KeepLast);
+    assert_eq!(map.unwrap()["x"], 3);
+    let (map, _) = deserialize::<HashMap<String, i32>>(DuplicateKeys::KeepFirst);
+    assert_eq!(map.unwrap()["x"], 1);
+    let (map, _) = deserialize::<BTreeMap<String, i32>>(DuplicateKeys::KeepFirst);
+    assert_eq!(map.unwrap().len(), 2);
+
+    let (map, error) = deserialize::<BTreeMap<String, i32>>(DuplicateKeys::Error);
+    assert!(map.is_none());
+    assert_eq!(
+        error,
+        Some(DeserializeError::DuplicateKey {
+            key: "x".to_string(),
+            first: Some(6),
+            second: Some(22),
+        })
+    );
+    assert_eq!(
+        error.unwrap().to_string(),
+        "duplicate key `x`, first at 6 and again at 22"
+    );
+}
+
+#[test]
+fn thing() {
+    let (thing, _) = deserialize::<Thing>(DuplicateKeys::KeepFirst);
+    assert_eq!(thing.unwrap().get_i64("x"), Some(1));
+
+    let (thing, error) = deserialize::<Thing>(DuplicateKeys::Error);
+    assert!(thing.is_none());
+    assert!(matches!(error, Some(DeserializeError::DuplicateKey { .. })));
+}
+
+#[test]
+fn keep_first_skips_nested_values() {
+    let source = r#"{"a": [1, {"b": 2}], "a": {"c": [3]}, "d": 4}"#;
+    let mut deserializer =
+        JSONDeserializer::new(source).with_duplicate_keys(DuplicateKeys::KeepFirst);
+    let map = HashMap::<String, Thing>::deserialize(&mut deserializer).unwrap();
+    assert!(map["a"].is_array());
+    assert_eq!(map["d"], Thing::Integer(4));
+    assert_eq!(map.len(), 2);
+}
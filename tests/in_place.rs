@@ -0,0 +1,38 @@
+use kserde::*;
+
+#[test]
+fn vec_reuses_allocations() {
+    let mut values = vec![String::with_capacity(64), String::new(), String::new()];
+    let vec_pointer = values.as_ptr();
+    let string_pointer = values[0].as_ptr();
+
+    let mut deserializer = JSONDeserializer::new("[\"a\", \"b\"]");
+    values.deserialize_in_place(&mut deserializer).unwrap();
+
+    assert_eq!(values, ["a", "b"]);
+    assert_eq!(values.as_ptr(), vec_pointer);
+    assert_eq!(values[0].as_ptr(), string_pointer);
+
+    let mut deserializer = JSONDeserializer::new("[\"a\", \"b\", \"c\", \"d\"]");
+    values.deserialize_in_place(&mut deserializer).unwrap();
+    assert_eq!(values, ["a", "b", "c", "d"]);
+}
+
+#[test]
+fn nested_arrays() {
+    let mut grid = [[0u8; 2]; 2];
+    let mut deserializer = JSONDeserializer::new("[[1, 2], [3, 4]]");
+    grid.deserialize_in_place(&mut deserializer).unwrap();
+    assert_eq!(grid, [[1, 2], [3, 4]]);
+
+    let mut deserializer = JSONDeserializer::new("[[1, 2]]");
+    assert_eq!(grid.deserialize_in_place(&mut deserializer), None);
+}
+
+#[test]
+fn default_replaces_value() {
+    let mut value = 5i32;
+    let mut deserializer = JSONDeserializer::new("7");
+    value.deserialize_in_place(&mut deserializer).unwrap();
+    assert_eq!(value, 7);
+}
@@ -0,0 +1,87 @@
+use kserde::*;
+
+#[test]
+fn i64_beyond_f64_precision() {
+    let big = 9_007_199_254_740_993i64; // 2^53 + 1
+    assert_eq!(i64::from_json(&big.to_json()), Some(big));
+    assert_eq!(i64::from_json("9223372036854775807"), Some(i64::MAX));
+    assert_eq!(i64::from_json("-9223372036854775808"), Some(i64::MIN));
+}
+
+#[test]
+fn i64_overflow_fails() {
+    assert_eq!(i64::from_json("9223372036854775808"), None);
+    assert_eq!(i64::from_json("-9223372036854775809"), None);
+}
+
+#[test]
+fn i64_rejects_fraction_and_exponent() {
+    assert_eq!(i64::from_json("1.7"), None);
+    assert_eq!(i64::from_json("1e3"), None);
+    assert_eq!(f64::from_json("1.5"), Some(1.5));
+}
+
+#[test]
+fn thing_keeps_exact_integers() {
+    let thing = Thing::from_json("[9007199254740993, 1.5, -3]").unwrap();
+    let array = thing.array().unwrap();
+    assert_eq!(array[0].integer(), Some(9_007_199_254_740_993));
+    assert_eq!(array[1].integer(), None);
+    assert_eq!(array[1].number(), Some(1.5));
+    assert_eq!(array[2].number(), Some(-3.0));
+    assert_eq!(thing.to_json(), "[9007199254740993, 1.5, -3]");
+}
+
+#[test]
+fn thing_keeps_integers_beyond_i64() {
+    let max = Thing::from_json("18446744073709551615").unwrap();
+    assert!(max.is_integer());
+    assert_eq!(max.unsigned(), Some(u64::MAX));
+    assert_eq!(max.to_json(), "18446744073709551615");
+
+    let json =
+        "[-170141183460469231731687303715884105728, 340282366920938463463374607431768211455]";
+    assert_eq!(Thing::from_json(json).unwrap().to_json(), json);
+    assert!(
+        Thing::from_json("[9223372036854775808]").unwrap()
+            != Thing::from_json("[9223372036854775809]").unwrap()
+    );
+}
+
+#[test]
+fn unsigned_and_128_bit_round_trip() {
+    assert_eq!(u64::from_json(&u64::MAX.to_json()), Some(u64::MAX));
+    assert_eq!(i128::from_json(&i128::MIN.to_json()), Some(i128::MIN));
+    assert_eq!(u128::from_json(&u128::MAX.to_json()), Some(u128::MAX));
+    assert_eq!(usize::from_json("42"), Some(42));
+    assert_eq!(i8::from_json("-128"), Some(-128));
+}
+
+#[test]
+fn out_of_range_integers_fail() {
+    assert_eq!(u8::from_json("256"), None);
+    assert_eq!(i8::from_json("-129"), None);
+    assert_eq!(u32::from_json("-1"), None);
+    assert_eq!(usize::from_json("-1"), None);
+    assert_eq!(i32::from_json("2147483648"), None);
+    assert_eq!(
+        u128::from_json("340282366920938463463374607431768211456"),
+        None
+    );
+}
+
+#[test]
+fn floats_round_correctly() {
+    assert_eq!(
+        f64::from_json("333333333.33333329"),
+        Some(333333333.3333333)
+    );
+    assert_eq!(
+        f64::from_json("2.2250738585072014e-308"),
+        Some(f64::MIN_POSITIVE)
+    );
+    assert_eq!(f64::from_json("1e0"), Some(1.0));
+    assert_eq!(f64::from_json("-0.5E+2"), Some(-50.0));
+    assert_eq!(f64::from_json("1."), None);
+    assert_eq!(f64::from_json("1e"), None);
+}
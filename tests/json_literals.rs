@@ -0,0 +1,16 @@
+use kserde::*;
+use std::collections::HashMap;
+
+#[test]
+fn literals_followed_by_values() {
+    assert_eq!(
+        Vec::<bool>::from_json("[true,false,true]"),
+        Some(vec![true, false, true])
+    );
+    assert_eq!(
+        Vec::<bool>::from_json("[false, true]"),
+        Some(vec![false, true])
+    );
+    let map: HashMap<String, bool> = HashMap::from_json(r#"{"a":true,"b":false}"#).unwrap();
+    assert!(map["a"] && !map["b"]);
+}
@@ -0,0 +1,22 @@
+use kserde::*;
+
+#[test]
+fn strings_are_escaped() {
+    let s = "quote \" backslash \\ newline \n tab \t bell \u{7}".to_string();
+    let json = s.to_json();
+    assert_eq!(
+        json,
+        r#""quote \" backslash \\ newline \n tab \t bell \u0007""#
+    );
+    assert_eq!(String::from_json(&json), Some(s));
+}
+
+#[test]
+fn empty_containers_in_pretty_output() {
+    let value = thing!({"a": [[], 1], "b": {}, "c": 2});
+    assert_eq!(
+        value.to_json(),
+        "{\n    \"a\": [[], 1],\n    \"b\": {\n    },\n    \"c\": 2\n}"
+    );
+    assert_eq!(ThingOwned::from_json(&value.to_json()), Some(value));
+}
@@ -0,0 +1,86 @@
+use kserde::*;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct EntityId(u32);
+
+impl SerializeKey for EntityId {
+    fn to_key(&self) -> MapKey<'_> {
+        self.0.to_key()
+    }
+}
+
+impl<'a> DeserializeKey<'a> for EntityId {
+    fn from_key(key: MapKey<'a>) -> Option<Self> {
+        u32::from_key(key).map(EntityId)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Layer {
+    Background,
+    Foreground,
+}
+
+impl SerializeKey for Layer {
+    fn to_key(&self) -> MapKey<'_> {
+        MapKey::String(
+            match self {
+                Layer::Background => "Background",
+                Layer::Foreground => "Foreground",
+            }
+            .into(),
+        )
+    }
+}
+
+impl<'a> DeserializeKey<'a> for Layer {
+    fn from_key(key: MapKey<'a>) -> Option<Self> {
+        match &*key.to_str() {
+            "Background" => Some(Layer::Background),
+            "Foreground" => Some(Layer::Foreground),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn integer_keys() {
+    let mut map = BTreeMap::new();
+    map.insert(-3i64, "a".to_string());
+    map.insert(10, "b".to_string());
+    let json = map.to_json();
+    assert_eq!(json, "{\n    \"-3\": \"a\",\n    \"10\": \"b\"\n}");
+    assert_eq!(BTreeMap::from_json(&json), Some(map));
+
+    assert_eq!(HashMap::<u8, i32>::from_json("{\"256\": 1}"), None);
+    assert_eq!(HashMap::<u8, i32>::from_json("{\"x\": 1}"), None);
+}
+
+#[test]
+fn bool_and_char_keys() {
+    let mut map = HashMap::new();
+    map.insert(true, 1);
+    assert_eq!(map.to_json(), "{\n    \"true\": 1\n}");
+    assert_eq!(HashMap::from_json(&map.to_json()), Some(map));
+
+    let mut map = BTreeMap::new();
+    map.insert('x', 1);
+    map.insert('é', 2);
+    assert_eq!(BTreeMap::from_json(&map.to_json()), Some(map));
+    assert_eq!(BTreeMap::<char, i32>::from_json("{\"xy\": 1}"), None);
+}
+
+#[test]
+fn newtype_and_enum_keys() {
+    let mut entities = HashMap::new();
+    entities.insert(EntityId(7), "player".to_string());
+    assert_eq!(HashMap::from_json(&entities.to_json()), Some(entities));
+
+    let mut layers = BTreeMap::new();
+    layers.insert(Layer::Foreground, 1);
+    layers.insert(Layer::Background, 0);
+    let json = layers.to_json();
+    assert_eq!(json, "{\n    \"Background\": 0,\n    \"Foreground\": 1\n}");
+    assert_eq!(BTreeMap::from_json(&json), Some(layers));
+}
@@ -0,0 +1,32 @@
+use kserde::*;
+
+#[test]
+fn option_round_trip() {
+    assert_eq!(Option::<i32>::from_json("null"), Some(None));
+    assert_eq!(Option::<i32>::from_json(" 5"), Some(Some(5)));
+    assert_eq!(Option::<i32>::from_json("nope"), None);
+    assert_eq!(
+        Vec::<Option<bool>>::from_json("[true, null, false]"),
+        Some(vec![Some(true), None, Some(false)])
+    );
+    assert_eq!(vec![Some(1), None].to_json(), "[1, null]");
+
+    let thing = to_thing(&vec![None, Some("a")]);
+    assert_eq!(
+        from_thing::<Vec<Option<String>>, _>(&thing),
+        Some(vec![None, Some("a".to_string())])
+    );
+}
+
+#[test]
+fn option_in_place() {
+    let mut value = Some(vec![1, 2, 3]);
+    value
+        .deserialize_in_place(&mut JSONDeserializer::new("[4]"))
+        .unwrap();
+    assert_eq!(value, Some(vec![4]));
+    value
+        .deserialize_in_place(&mut JSONDeserializer::new("null"))
+        .unwrap();
+    assert_eq!(value, None);
+}
@@ -0,0 +1,142 @@
+use kserde::*;
+
+fn json(source: &str) -> ThingOwned {
+    ThingOwned::from_json(source).unwrap()
+}
+
+#[test]
+fn merge_patch() {
+    let mut target = json(
+        r#"{"title": "Goodbye!", "author": {"givenName": "John", "familyName": "Doe"}, "tags": ["example", "sample"], "content": "This will be unchanged"}"#,
+    );
+    let patch = json(
+        r#"{"title": "Hello!", "phoneNumber": "+01-555-1234", "author": {"familyName": null}, "tags": ["example"]}"#,
+    );
+    target.merge_patch(&patch);
+    assert_eq!(
+        target.to_json(),
+        json(r#"{"title": "Hello!", "author": {"givenName": "John"}, "tags": ["example"], "content": "This will be unchanged", "phoneNumber": "+01-555-1234"}"#).to_json()
+    );
+
+    let mut target = json(r#"["a"]"#);
+    target.merge_patch(&json(r#"{"a": {"b": "c"}}"#));
+    assert_eq!(target.pointer("/a/b").unwrap().as_str(), Some("c"));
+
+    let mut target = json(r#"{"a": "b"}"#);
+    target.merge_patch(&json("null"));
+    assert!(target.is_null());
+}
+
+#[test]
+fn apply_patch() {
+    let mut target = json(r#"{"foo": ["bar", "baz"], "a~b": {"c": 1}, "n": 2}"#);
+    let patch = json(
+        r#"[
+        {"op": "add", "path": "/foo/1", "value": "qux"},
+        {"op": "add", "path": "/foo/-", "value": "end"},
+        {"op": "remove", "path": "/foo/0"},
+        {"op": "replace", "path": "/a~0b/c", "value": 2},
+        {"op": "copy", "from": "/a~0b", "path": "/copy"},
+        {"op": "move", "from": "/copy/c", "path": "/moved"},
+        {"op": "test", "path": "/n", "value": 2.0},
+        {"op": "test", "path": "/foo", "value": ["qux", "baz", "end"]}
+    ]"#,
+    );
+    target.apply_patch(&patch).unwrap();
+    assert_eq!(
+        target.to_json(),
+        json(r#"{"foo": ["qux", "baz", "end"], "a~b": {"c": 2}, "n": 2, "copy": {}, "moved": 2}"#)
+            .to_json()
+    );
+}
+
+#[test]
+fn apply_patch_errors() {
+    let original = json(r#"{"a": [1, 2], "b": {"c": true}}"#);
+
+    let mut target = original.clone();
+    let error = target
+        .apply_patch(&json(r#"[{"op": "remove", "path": "/a/0"}, {"op": "replace", "path": "/missing", "value": 1}]"#))
+        .unwrap_err();
+    assert_eq!(error.index, 1);
+    assert_eq!(error.op, "replace");
+    assert_eq!(error.pointer, "/missing");
+    assert_eq!(error.kind, PatchErrorKind::PathNotFound);
+    assert_eq!(
+        error.to_string(),
+        "patch operation 1 (\"replace\" at \"/missing\"): path not found"
+    );
+    // Nothing is applied if any operation fails.
+    assert_eq!(target.to_json(), original.to_json());
+
+    let pointer = |patch: &str| {
+        original
+            .clone()
+            .apply_patch(&json(patch))
+            .unwrap_err()
+            .pointer
+    };
+    assert_eq!(
+        pointer(r#"[{"op": "move", "from": "/x", "path": "/y"}]"#),
+        "/x"
+    );
+    assert_eq!(
+        pointer(r#"[{"op": "copy", "from": "/x", "path": "/y"}]"#),
+        "/x"
+    );
+    assert_eq!(
+        pointer(r#"[{"op": "copy", "from": "/a", "path": "/x/y"}]"#),
+        "/x/y"
+    );
+
+    let kind = |patch: &str| original.clone().apply_patch(&json(patch)).unwrap_err().kind;
+    assert_eq!(
+        kind(r#"[{"op": "test", "path": "/b/c", "value": false}]"#),
+        PatchErrorKind::TestFailed
+    );
+    assert_eq!(
+        kind(r#"[{"op": "move", "from": "/b", "path": "/b/d"}]"#),
+        PatchErrorKind::MoveIntoChild
+    );
+    assert_eq!(
+        kind(r#"[{"op": "add", "path": "/a/3", "value": 1}]"#),
+        PatchErrorKind::PathNotFound
+    );
+    assert_eq!(
+        kind(r#"[{"op": "frobnicate", "path": "/a"}]"#),
+        PatchErrorKind::UnknownOperation
+    );
+    assert_eq!(
+        kind(r#"[{"op": "add", "path": "/a"}]"#),
+        PatchErrorKind::MalformedOperation
+    );
+}
+
+#[test]
+fn diff_produces_patch() {
+    let a = json(
+        r#"{"same": 1, "changed": {"x": 1, "y": 2}, "gone": true, "list": [1, 2, 3], "w/s": 0}"#,
+    );
+    let b =
+        json(r#"{"same": 1, "changed": {"x": 1, "y": 3}, "list": [1, 5], "new": null, "w/s": 1}"#);
+    let patch = diff(&a, &b);
+    assert_eq!(
+        patch.to_json(),
+        json(
+            r#"[
+            {"op": "replace", "path": "/changed/y", "value": 3},
+            {"op": "remove", "path": "/gone"},
+            {"op": "replace", "path": "/list/1", "value": 5},
+            {"op": "remove", "path": "/list/2"},
+            {"op": "replace", "path": "/w~1s", "value": 1},
+            {"op": "add", "path": "/new", "value": null}
+        ]"#
+        )
+        .to_json()
+    );
+
+    let mut patched = a.clone();
+    patched.apply_patch(&patch).unwrap();
+    assert!(diff(&patched, &b).array().unwrap().is_empty());
+    assert!(diff(&a, &a).array().unwrap().is_empty());
+}
@@ -0,0 +1,48 @@
+use kserde::*;
+use std::collections::HashMap;
+
+fn to_sorted_json<T: Serialize<JSONSerializer<()>>>(value: &T) -> String {
+    let mut serializer = JSONSerializer::new().with_sorted_keys(true);
+    value.serialize(&mut serializer);
+    serializer.done()
+}
+
+#[test]
+fn hash_maps_sort_by_key() {
+    let map: HashMap<String, i32> = (0..10).map(|i| (format!("k{}", i), i)).collect();
+    let expected: String = (0..10)
+        .map(|i| format!("\n    \"k{}\": {}", i, i))
+        .collect::<Vec<_>>()
+        .join(",");
+    assert_eq!(to_sorted_json(&map), format!("{{{}\n}}", expected));
+
+    // Integer keys sort numerically rather than as strings.
+    let map: HashMap<u32, bool> = [(10, true), (9, false), (100, true)]
+        .iter()
+        .copied()
+        .collect();
+    assert_eq!(
+        to_sorted_json(&map),
+        "{\n    \"9\": false,\n    \"10\": true,\n    \"100\": true\n}"
+    );
+}
+
+#[test]
+fn things_sort_by_key() {
+    let thing = thing!({"b": 1, "a": {"d": 2, "c": 3}});
+    assert_eq!(
+        to_sorted_json(&thing),
+        "{\n    \"a\": {\n        \"c\": 3,\n        \"d\": 2\n    },\n    \"b\": 1\n}"
+    );
+    // Without the option insertion order is kept.
+    assert_eq!(
+        thing.to_json(),
+        "{\n    \"b\": 1,\n    \"a\": {\n        \"d\": 2,\n        \"c\": 3\n    }\n}"
+    );
+
+    let borrowed = Thing::from_json("{\"y\": null, \"x\": null}").unwrap();
+    assert_eq!(
+        to_sorted_json(&borrowed),
+        "{\n    \"x\": null,\n    \"y\": null\n}"
+    );
+}
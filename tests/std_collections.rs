@@ -0,0 +1,59 @@
+use kserde::*;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn maps() {
+    let mut b_tree_map = BTreeMap::new();
+    b_tree_map.insert("b".to_string(), 2);
+    b_tree_map.insert("a".to_string(), 1);
+    let json = b_tree_map.to_json();
+    assert_eq!(json, "{\n    \"a\": 1,\n    \"b\": 2\n}");
+    assert_eq!(BTreeMap::<String, i32>::from_json(&json), Some(b_tree_map));
+
+    type FixedState = std::hash::BuildHasherDefault<std::collections::hash_map::DefaultHasher>;
+    let mut hash_map: HashMap<String, bool, FixedState> = HashMap::default();
+    hash_map.insert("x".to_string(), true);
+    assert_eq!(HashMap::from_json(&hash_map.to_json()), Some(hash_map));
+}
+
+#[test]
+fn sequences() {
+    let b_tree_set: BTreeSet<i32> = [3, 1, 2].iter().copied().collect();
+    assert_eq!(b_tree_set.to_json(), "[1, 2, 3]");
+    assert_eq!(BTreeSet::from_json("[3, 1, 2, 1]"), Some(b_tree_set));
+
+    let hash_set: HashSet<String> = HashSet::from_json("[\"a\", \"b\"]").unwrap();
+    assert!(hash_set.contains("a") && hash_set.contains("b"));
+
+    let vec_deque: VecDeque<u8> = VecDeque::from_json("[1, 2]").unwrap();
+    assert_eq!(vec_deque.to_json(), "[1, 2]");
+
+    let linked_list: LinkedList<u8> = LinkedList::from_json("[4, 5]").unwrap();
+    assert_eq!(linked_list.to_json(), "[4, 5]");
+
+    let binary_heap: BinaryHeap<i32> = BinaryHeap::from_json("[1, 9, 5]").unwrap();
+    assert_eq!(binary_heap.into_sorted_vec(), vec![1, 5, 9]);
+
+    assert_eq!(VecDeque::<u8>::from_json("[1, 256]"), None);
+}
+
+#[test]
+fn pointers_and_cells() {
+    assert_eq!(Box::<i32>::from_json("5"), Some(Box::new(5)));
+    assert_eq!(Rc::<String>::from_json("\"a\"").unwrap().to_json(), "\"a\"");
+    assert_eq!(*Arc::<bool>::from_json("true").unwrap(), true);
+    assert_eq!(Cell::<i32>::from_json("3").unwrap().to_json(), "3");
+    assert_eq!(RefCell::<i32>::from_json("4").unwrap().to_json(), "4");
+    assert_eq!(Mutex::<i32>::from_json("6").unwrap().to_json(), "6");
+
+    let boxed_str: Box<str> = "hello".into();
+    assert_eq!(boxed_str.to_json(), "\"hello\"");
+
+    let cow: Cow<[i32]> = Cow::from_json("[1, 2]").unwrap();
+    assert_eq!(&*cow, &[1, 2]);
+    assert_eq!(cow.to_json(), "[1, 2]");
+}
@@ -0,0 +1,92 @@
+use kserde::*;
+use std::marker::PhantomData;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::num::{NonZeroU32, Wrapping};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[test]
+fn char_round_trip() {
+    assert_eq!('é'.to_json(), "\"é\"");
+    assert_eq!(char::from_json("\"é\""), Some('é'));
+    assert_eq!(char::from_json("\"ab\""), None);
+    assert_eq!(char::from_json("\"\""), None);
+}
+
+#[test]
+fn time() {
+    let duration = Duration::new(5, 300);
+    assert_eq!(Duration::from_json(&duration.to_json()), Some(duration));
+    assert_eq!(
+        Duration::from_json("{\"secs\": 1, \"nanos\": 1000000000}"),
+        None
+    );
+    assert_eq!(
+        Duration::from_json(r#"{"secs": 1, "extra": [5], "nanos": 2}"#),
+        Some(Duration::new(1, 2))
+    );
+
+    let now = SystemTime::now();
+    assert_eq!(SystemTime::from_json(&now.to_json()), Some(now));
+
+    let before_epoch = UNIX_EPOCH - Duration::new(10, 250);
+    assert_eq!(
+        SystemTime::from_json(&before_epoch.to_json()),
+        Some(before_epoch)
+    );
+    assert_eq!(
+        SystemTime::from_json(
+            r#"{"secs_since_epoch": 1, "extra": {"a": 5}, "nanos_since_epoch": 2}"#
+        ),
+        Some(UNIX_EPOCH + Duration::new(1, 2))
+    );
+}
+
+#[test]
+fn paths_and_addresses() {
+    let path = PathBuf::from("assets/textures/grass.png");
+    assert_eq!(PathBuf::from_json(&path.to_json()), Some(path));
+
+    let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1));
+    assert_eq!(ip.to_json(), "\"192.168.0.1\"");
+    assert_eq!(IpAddr::from_json(&ip.to_json()), Some(ip));
+    assert_eq!(IpAddr::from_json("\"::1\""), Some("::1".parse().unwrap()));
+    assert_eq!(IpAddr::from_json("\"192.168.0.256\""), None);
+
+    let socket: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+    assert_eq!(SocketAddr::from_json(&socket.to_json()), Some(socket));
+    assert_eq!(SocketAddr::from_json("\"127.0.0.1\""), None);
+}
+
+#[test]
+fn numeric_wrappers() {
+    let non_zero = NonZeroU32::new(3).unwrap();
+    assert_eq!(NonZeroU32::from_json(&non_zero.to_json()), Some(non_zero));
+    assert_eq!(NonZeroU32::from_json("0"), None);
+
+    assert_eq!(Wrapping(7u8).to_json(), "7");
+    assert_eq!(Wrapping::<u8>::from_json("7"), Some(Wrapping(7)));
+}
+
+#[test]
+fn ranges_and_phantom_data() {
+    let range = 2..5;
+    assert_eq!(std::ops::Range::from_json(&range.to_json()), Some(range));
+    let range = 2..=5;
+    assert_eq!(
+        std::ops::RangeInclusive::from_json(&range.to_json()),
+        Some(range)
+    );
+    assert_eq!(std::ops::Range::<i32>::from_json("{\"start\": 1}"), None);
+    assert_eq!(
+        std::ops::Range::from_json(r#"{"start": 1, "step": 2, "end": 4}"#),
+        Some(1..4)
+    );
+    assert_eq!(
+        std::ops::RangeInclusive::from_json(r#"{"start": 1, "step": 2, "end": 4}"#),
+        Some(1..=4)
+    );
+
+    assert_eq!(PhantomData::<String>.to_json(), "null");
+    assert_eq!(PhantomData::<String>::from_json("null"), Some(PhantomData));
+}
@@ -0,0 +1,67 @@
+use kserde::*;
+
+const DOCUMENT: &str = r#"{
+    "name": "crate",
+    "count": 3,
+    "ratio": 0.5,
+    "enabled": true,
+    "items": [{"id": 1}, {"id": 2}],
+    "a/b": 1,
+    "m~n": 2,
+    "": 3
+}"#;
+
+#[test]
+fn index_and_getters() {
+    let thing = Thing::from_json(DOCUMENT).unwrap();
+    assert_eq!(thing["name"].as_str(), Some("crate"));
+    assert_eq!(thing["items"][1]["id"].integer(), Some(2));
+    assert!(thing["missing"]["deeper"][5].is_null());
+    assert!(thing[0].is_null());
+
+    assert_eq!(thing.get_str("name"), Some("crate"));
+    assert_eq!(thing.get_i64("count"), Some(3));
+    assert_eq!(thing.get_u64("count"), Some(3));
+    assert_eq!(thing.get_f64("count"), Some(3.0));
+    assert_eq!(thing.get_f64("ratio"), Some(0.5));
+    assert_eq!(thing.get_i64("ratio"), None);
+    assert_eq!(thing.get_bool("enabled"), Some(true));
+    assert_eq!(thing.get_array("items").map(|a| a.len()), Some(2));
+    assert_eq!(thing.get_str("count"), None);
+
+    let owned = thing.to_owned();
+    assert_eq!(owned["items"][0]["id"].integer(), Some(1));
+    assert_eq!(owned.get_str("name"), Some("crate"));
+    assert!(owned["name"][0].is_null());
+}
+
+#[test]
+fn predicates() {
+    let thing = ThingOwned::from_json(DOCUMENT).unwrap();
+    assert!(thing.is_object());
+    assert!(thing["items"].is_array());
+    assert!(thing["count"].is_integer() && thing["count"].is_number());
+    assert!(thing["ratio"].is_number() && !thing["ratio"].is_integer());
+    assert!(thing["enabled"].is_bool());
+    assert!(thing["name"].is_string());
+    assert!(!thing["name"].is_bytes());
+}
+
+#[test]
+fn json_pointer() {
+    let thing = Thing::from_json(DOCUMENT).unwrap();
+    assert!(thing.pointer("").unwrap().is_object());
+    assert_eq!(thing.pointer("/items/1/id").unwrap().integer(), Some(2));
+    assert_eq!(thing.pointer("/a~1b").unwrap().integer(), Some(1));
+    assert_eq!(thing.pointer("/m~0n").unwrap().integer(), Some(2));
+    assert_eq!(thing.pointer("/").unwrap().integer(), Some(3));
+
+    assert!(thing.pointer("name").is_none());
+    assert!(thing.pointer("/items/01").is_none());
+    assert!(thing.pointer("/items/-").is_none());
+    assert!(thing.pointer("/items/2").is_none());
+    assert!(thing.pointer("/name/0").is_none());
+
+    let owned = thing.to_owned();
+    assert_eq!(owned.pointer("/items/0/id").unwrap().integer(), Some(1));
+}
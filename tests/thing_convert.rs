@@ -0,0 +1,77 @@
+use kserde::*;
+use std::collections::BTreeMap;
+
+#[test]
+fn round_trip_through_thing() {
+    let mut value = BTreeMap::new();
+    value.insert("a".to_string(), (1u8, vec![0.5f32, 2.0], true));
+    value.insert("b".to_string(), (2u8, Vec::new(), false));
+
+    let thing = to_thing(&value);
+    assert_eq!(thing.pointer("/a/1/0").unwrap().number(), Some(0.5));
+    assert_eq!(thing.pointer("/b/0").unwrap().integer(), Some(2));
+    assert_eq!(thing.to_json(), value.to_json());
+
+    let back: BTreeMap<String, (u8, Vec<f32>, bool)> = from_thing(&thing).unwrap();
+    assert_eq!(back, value);
+}
+
+#[test]
+fn integers_outside_i64() {
+    assert!(to_thing(&u64::MAX).is_integer());
+    assert_eq!(from_thing::<u64, _>(&to_thing(&u64::MAX)), Some(u64::MAX));
+    let above_i64 = (1u64 << 63) + 1;
+    assert_eq!(from_thing::<u64, _>(&to_thing(&above_i64)), Some(above_i64));
+    assert_eq!(
+        from_thing::<i128, _>(&to_thing(&i128::MIN)),
+        Some(i128::MIN)
+    );
+    assert_eq!(
+        from_thing::<u128, _>(&to_thing(&u128::MAX)),
+        Some(u128::MAX)
+    );
+    assert_eq!(to_thing(&u128::MAX).to_json(), u128::MAX.to_json());
+    assert_eq!(from_thing::<i128, _>(&to_thing(&-5i128)), Some(-5));
+    assert_eq!(from_thing::<u8, _>(&to_thing(&300)), None);
+    assert_eq!(from_thing::<i64, _>(&ThingOwned::Number(1.5)), None);
+}
+
+#[test]
+fn integers_from_whole_numbers() {
+    assert_eq!(from_thing::<u8, _>(&ThingOwned::Number(200.0)), Some(200));
+    assert_eq!(
+        from_thing::<i128, _>(&ThingOwned::Number(-(2f64.powi(127)))),
+        Some(i128::MIN)
+    );
+    assert_eq!(
+        from_thing::<i128, _>(&ThingOwned::Number(2f64.powi(127))),
+        None
+    );
+    assert_eq!(from_thing::<i128, _>(&ThingOwned::Number(1e40)), None);
+    assert_eq!(from_thing::<i128, _>(&ThingOwned::Number(-1e40)), None);
+    assert_eq!(from_thing::<u128, _>(&ThingOwned::Number(1e40)), None);
+}
+
+#[test]
+fn borrowing_from_thing() {
+    let thing = Thing::from_json("{\"name\": \"kserde\", \"data\": \"AAEC\"}").unwrap();
+    let name: &str = from_thing(&thing["name"]).unwrap();
+    assert_eq!(name, "kserde");
+
+    // Bytes written as base64 strings by the JSON serializer can be read back.
+    let data: Bytes = from_thing(&thing["data"]).unwrap();
+    assert_eq!(data.0, [0, 1, 2]);
+
+    let bytes = to_thing(&Bytes(vec![3u8, 4]));
+    assert_eq!(bytes.bytes().map(|b| b.len()), Some(2));
+    assert_eq!(from_thing::<&[u8], _>(&bytes), Some(&[3u8, 4][..]));
+}
+
+#[test]
+fn thing_to_thing() {
+    let source = "{\"z\": [1, 2.5, null, {\"y\": \"x\"}], \"a\": false}";
+    let thing = ThingOwned::from_json(source).unwrap();
+    let copy: ThingOwned = from_thing(&thing).unwrap();
+    assert_eq!(copy.to_json(), thing.to_json());
+    assert_eq!(to_thing(&thing).to_json(), thing.to_json());
+}
@@ -0,0 +1,86 @@
+use kserde::*;
+
+#[test]
+fn insert_remove_and_entry() {
+    let mut thing = ThingOwned::from_json("{\"b\": 1, \"a\": 2}").unwrap();
+    assert!(thing.insert("c", "three").is_none());
+    assert_eq!(thing.insert("b", 10).and_then(|t| t.integer()), Some(1));
+    assert_eq!(thing.remove("a").and_then(|t| t.integer()), Some(2));
+    assert!(thing.remove("a").is_none());
+
+    *thing.entry("d") = true.into();
+    thing.entry("e").push(1);
+    thing.entry("e").push(2.5);
+    assert_eq!(
+        thing.to_json(),
+        "{\n    \"b\": 10,\n    \"c\": \"three\",\n    \"d\": true,\n    \"e\": [1, 2.5]\n}"
+    );
+
+    let mut null = ThingOwned::Null;
+    null.insert("x", Some(1));
+    null.insert("y", None::<i32>);
+    assert!(null["x"].is_integer() && null["y"].is_null() && null.get("y").is_some());
+}
+
+#[test]
+#[should_panic]
+fn insert_into_array_panics() {
+    let mut thing = ThingOwned::Array(Vec::new());
+    thing.insert("a", 1);
+}
+
+#[test]
+fn get_mut_pointer_mut_and_take() {
+    let mut thing = ThingOwned::from_json("{\"items\": [{\"id\": 1}, {\"id\": 2}]}").unwrap();
+    *thing.pointer_mut("/items/1/id").unwrap() = 5.into();
+    assert_eq!(thing.pointer("/items/1/id").unwrap().integer(), Some(5));
+    assert!(thing.pointer_mut("/items/2").is_none());
+
+    thing
+        .get_mut("items")
+        .unwrap()
+        .get_index_mut(0)
+        .unwrap()
+        .insert("name", "first");
+    assert_eq!(thing["items"][0].get_str("name"), Some("first"));
+
+    let items = thing.get_mut("items").unwrap().take();
+    assert_eq!(items.array().map(|a| a.len()), Some(2));
+    assert!(thing["items"].is_null());
+}
+
+#[test]
+fn thing_macro() {
+    let id = 7;
+    let key = "dynamic";
+    let value = thing!({
+        "id": id,
+        "negative": -1,
+        "ratio": 0.25 * 2.0,
+        "tags": ["a", "b", null, [], {}],
+        "nested": { "ok": true, "none": null },
+        (key): vec![1, 2],
+    });
+    assert_eq!(value.get_i64("id"), Some(7));
+    assert_eq!(value.get_i64("negative"), Some(-1));
+    assert_eq!(value.get_f64("ratio"), Some(0.5));
+    assert_eq!(value["tags"][1].as_str(), Some("b"));
+    assert!(value["tags"][2].is_null());
+    assert!(value["tags"][3].is_array() && value["tags"][4].is_object());
+    assert_eq!(value.pointer("/nested/ok").unwrap().bool(), Some(true));
+    assert_eq!(value["dynamic"][1].integer(), Some(2));
+
+    let keys: Vec<_> = value.object().unwrap().keys().cloned().collect();
+    assert_eq!(
+        keys,
+        ["id", "negative", "ratio", "tags", "nested", "dynamic"]
+    );
+
+    let items = vec!["a", "b"];
+    assert_eq!(thing!(items.len()).unsigned(), Some(2));
+    assert_eq!(thing!(-3isize).integer(), Some(-3));
+
+    assert!(thing!(null).is_null());
+    assert_eq!(thing!([]).array().map(|a| a.len()), Some(0));
+    assert_eq!(thing!("text").as_str(), Some("text"));
+}
@@ -0,0 +1,86 @@
+use kserde::*;
+use std::collections::{BTreeSet, HashSet};
+
+fn json(source: &str) -> ThingOwned {
+    ThingOwned::from_json(source).unwrap()
+}
+
+#[test]
+fn structural_equality() {
+    assert_eq!(
+        json(r#"{"a": 1, "b": [true, null, "x"]}"#),
+        json(r#"{"b": [true, null, "x"], "a": 1.0}"#)
+    );
+    assert_ne!(json(r#"{"a": 1}"#), json(r#"{"a": 1, "b": 2}"#));
+    assert_ne!(json(r#"[1, 2]"#), json(r#"[2, 1]"#));
+    assert_ne!(json("1"), json("\"1\""));
+    assert_ne!(
+        json("9007199254740993"),
+        ThingOwned::Number(9007199254740992.0)
+    );
+    assert_eq!(ThingOwned::Number(f64::NAN), ThingOwned::Number(f64::NAN));
+    assert_eq!(ThingOwned::Number(-0.0), ThingOwned::Integer(0));
+
+    let borrowed = Thing::from_json(r#"{"x": [1, 2.5]}"#).unwrap();
+    assert_eq!(
+        borrowed,
+        Thing::from_json(r#"{ "x": [1.0, 2.5] }"#).unwrap()
+    );
+}
+
+#[test]
+fn float_tolerance() {
+    let a = json("[0.1, 1]");
+    let b = ThingOwned::Array(vec![
+        ThingOwned::Number(0.1 + 1e-17 + 2e-17),
+        ThingOwned::Number(1.0000001),
+    ]);
+    assert!(!a.eq_with(&b, FloatComparison::Exact));
+    assert!(!a.eq_with(&b, FloatComparison::Ulps(4)));
+    assert!(a.eq_with(&b, FloatComparison::Epsilon(1e-6)));
+
+    let next = f64::from_bits(1.0f64.to_bits() + 2);
+    assert!(json("1").eq_with(&ThingOwned::Number(next), FloatComparison::Ulps(2)));
+    assert!(!json("1").eq_with(&ThingOwned::Number(next), FloatComparison::Ulps(1)));
+}
+
+#[test]
+fn ordering_and_hashing() {
+    let values = [
+        json(r#"{"b": 1, "a": 2}"#),
+        json(r#"[1, 2]"#),
+        json(r#""text""#),
+        json("2.5"),
+        json("2"),
+        json("-1"),
+        json("true"),
+        json("null"),
+        json(r#"{"a": 2, "b": 1}"#),
+        json("2.0"),
+    ];
+    let sorted: Vec<_> = values
+        .iter()
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    assert_eq!(
+        sorted,
+        [
+            json("null"),
+            json("true"),
+            json("-1"),
+            json("2"),
+            json("2.5"),
+            json(r#""text""#),
+            json("[1, 2]"),
+            json(r#"{"a": 2, "b": 1}"#),
+        ]
+    );
+
+    let set: HashSet<_> = values.iter().cloned().collect();
+    assert_eq!(set.len(), 8);
+    assert!(set.contains(&json(r#"{"a": 2.0, "b": 1}"#)));
+    assert!(json("-1") < ThingOwned::Number(-0.5));
+    assert!(ThingOwned::Number(f64::INFINITY) < ThingOwned::Number(f64::NAN));
+}
@@ -0,0 +1,45 @@
+use kserde::*;
+
+#[test]
+fn object_order_is_preserved() {
+    let source = "{\n    \"zebra\": 1,\n    \"apple\": [true, null],\n    \"mango\": {\n        \"b\": \"x\",\n        \"a\": 2.5\n    }\n}";
+    let thing = Thing::from_json(source).unwrap();
+    assert_eq!(thing.to_json(), source);
+    assert_eq!(thing.to_owned().to_json(), source);
+
+    let keys: Vec<_> = thing.object().unwrap().keys().map(|k| &**k).collect();
+    assert_eq!(keys, ["zebra", "apple", "mango"]);
+}
+
+#[test]
+fn duplicate_keys_policy() {
+    let source = "{\"a\": 1, \"b\": 2, \"a\": 3}";
+
+    let thing = Thing::from_json(source).unwrap();
+    let object = thing.object().unwrap();
+    assert_eq!(object.get("a").unwrap().integer(), Some(3));
+    assert_eq!(object.keys().next().unwrap(), "a");
+
+    let mut deserializer =
+        JSONDeserializer::new(source).with_duplicate_keys(DuplicateKeys::KeepFirst);
+    let thing = Thing::deserialize(&mut deserializer).unwrap();
+    assert_eq!(thing.object().unwrap().get("a").unwrap().integer(), Some(1));
+
+    let mut deserializer = JSONDeserializer::new(source).with_duplicate_keys(DuplicateKeys::Error);
+    assert!(Thing::deserialize(&mut deserializer).is_none());
+}
+
+#[test]
+fn ordered_map_remove_keeps_order() {
+    let mut map: OrderedMap<String, i32> = OrderedMap::new();
+    map.insert("a".into(), 1);
+    map.insert("b".into(), 2);
+    map.insert("c".into(), 3);
+    assert_eq!(map.remove("a"), Some(1));
+    assert_eq!(map.insert("b".into(), 4), Some(2));
+    assert_eq!(map.get("c"), Some(&3));
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        [(&"b".to_string(), &4), (&"c".to_string(), &3)]
+    );
+}
@@ -0,0 +1,152 @@
+use kserde::*;
+use std::borrow::Cow;
+
+/// Only implements the required methods, so wider integers and bytes use the defaults.
+struct LogSerializer {
+    log: Vec<String>,
+    context: (),
+}
+
+impl Serializer for LogSerializer {
+    type Result = Vec<String>;
+    type Context = ();
+
+    fn string(&mut self, s: &str) {
+        self.log.push(format!("string {}", s))
+    }
+    fn bool(&mut self, b: bool) {
+        self.log.push(format!("bool {}", b))
+    }
+    fn i64(&mut self, i: i64) {
+        self.log.push(format!("i64 {}", i))
+    }
+    fn f64(&mut self, n: f64) {
+        self.log.push(format!("f64 {}", n))
+    }
+    fn null(&mut self) {
+        self.log.push("null".to_string())
+    }
+    fn done(self) -> Self::Result {
+        self.log
+    }
+    fn begin_object(&mut self) {}
+    fn end_object(&mut self) {}
+    fn property<V: Serialize<Self>>(&mut self, _: &str, value: &V) {
+        value.serialize(self)
+    }
+    fn begin_array(&mut self) {
+        self.log.push("[".to_string())
+    }
+    fn end_array(&mut self) {
+        self.log.push("]".to_string())
+    }
+    fn value<V: Serialize<Self>>(&mut self, value: &V) {
+        value.serialize(self)
+    }
+    fn get_context(&self) -> &() {
+        &self.context
+    }
+    fn get_context_mut(&mut self) -> &mut () {
+        &mut self.context
+    }
+}
+
+/// Reads `i64`s, which may be in an array.
+struct I64Deserializer {
+    values: Vec<i64>,
+    context: (),
+}
+
+impl<'a> Deserializer<'a> for I64Deserializer {
+    type Context = ();
+
+    fn string(&mut self) -> Option<Cow<'a, str>> {
+        None
+    }
+    fn bool(&mut self) -> Option<bool> {
+        None
+    }
+    fn i64(&mut self) -> Option<i64> {
+        self.values.pop()
+    }
+    fn f64(&mut self) -> Option<f64> {
+        self.i64().map(|i| i as f64)
+    }
+    fn any<'b>(&'b mut self) -> Option<AnyValue<'a>> {
+        self.i64().map(|i| AnyValue::Integer(i.into()))
+    }
+    fn begin_object(&mut self) -> bool {
+        false
+    }
+    fn has_property(&mut self) -> Option<Cow<'a, str>> {
+        None
+    }
+    fn begin_array(&mut self) -> bool {
+        true
+    }
+    fn has_array_value(&mut self) -> bool {
+        !self.values.is_empty()
+    }
+    fn get_context_mut(&mut self) -> &mut () {
+        &mut self.context
+    }
+}
+
+#[test]
+fn serializer_defaults() {
+    let mut serializer = LogSerializer {
+        log: Vec::new(),
+        context: (),
+    };
+    serializer.u64(7);
+    serializer.u64(u64::MAX);
+    serializer.i128(-7);
+    serializer.u128(1 << 64);
+    assert_eq!(
+        serializer.done(),
+        [
+            "i64 7",
+            "f64 18446744073709552000",
+            "i64 -7",
+            "f64 18446744073709552000"
+        ]
+    );
+}
+
+#[test]
+fn deserializer_defaults() {
+    let deserializer = |value| I64Deserializer {
+        values: vec![value],
+        context: (),
+    };
+    assert_eq!(u64::deserialize(&mut deserializer(7)), Some(7));
+    assert_eq!(u64::deserialize(&mut deserializer(-7)), None);
+    assert_eq!(i128::deserialize(&mut deserializer(-7)), Some(-7));
+    assert_eq!(u128::deserialize(&mut deserializer(-7)), None);
+    assert_eq!(u8::deserialize(&mut deserializer(7)), Some(7));
+}
+
+#[test]
+fn bytes_defaults() {
+    let mut serializer = LogSerializer {
+        log: Vec::new(),
+        context: (),
+    };
+    serializer.bytes(&[1, 255]);
+    assert_eq!(serializer.done(), ["[", "i64 1", "i64 255", "]"]);
+
+    // Values are popped from the end.
+    let mut deserializer = I64Deserializer {
+        values: vec![255, 1],
+        context: (),
+    };
+    assert_eq!(
+        Bytes::<Vec<u8>>::deserialize(&mut deserializer).map(|b| b.0),
+        Some(vec![1, 255])
+    );
+    let mut deserializer = I64Deserializer {
+        values: vec![256],
+        context: (),
+    };
+    assert!(Bytes::<Vec<u8>>::deserialize(&mut deserializer).is_none());
+}
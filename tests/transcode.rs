@@ -0,0 +1,38 @@
+use kserde::*;
+
+const SOURCE: &str = "{\n    \"b\": [1, -2.5, \"three\", null],\n    \"a\": {\n        \"nested\": true,\n        \"empty\": []\n    }\n}";
+
+#[test]
+fn json_to_json() {
+    let mut deserializer = JSONDeserializer::new(SOURCE);
+    let mut serializer = JSONSerializer::new();
+    transcode(&mut deserializer, &mut serializer).unwrap();
+    assert_eq!(serializer.done(), SOURCE);
+}
+
+#[test]
+fn json_to_thing_and_back() {
+    let mut serializer = ThingSerializer::new();
+    transcode(&mut JSONDeserializer::new(SOURCE), &mut serializer).unwrap();
+    let thing = serializer.done();
+    assert_eq!(thing.pointer("/b/2").unwrap().as_str(), Some("three"));
+
+    let mut serializer = JSONSerializer::new();
+    transcode(&mut ThingDeserializer::new(&thing), &mut serializer).unwrap();
+    assert_eq!(serializer.done(), SOURCE);
+}
+
+#[test]
+fn integers_beyond_i64() {
+    let source =
+        "[9223372036854775809, 18446744073709551615, -170141183460469231731687303715884105728]";
+    let mut serializer = JSONSerializer::new();
+    transcode(&mut JSONDeserializer::new(source), &mut serializer).unwrap();
+    assert_eq!(serializer.done(), source);
+}
+
+#[test]
+fn malformed_input_fails() {
+    let mut serializer = JSONSerializer::new();
+    assert!(transcode(&mut JSONDeserializer::new("{\"a\": [1, }"), &mut serializer).is_none());
+}
@@ -0,0 +1,38 @@
+use kserde::*;
+
+#[test]
+fn tuples_round_trip() {
+    let pair = ("key".to_string(), 5u32);
+    let json = pair.to_json();
+    assert_eq!(json, "[\"key\", 5]");
+    assert_eq!(<(String, u32)>::from_json(&json), Some(pair));
+
+    let twelve = (1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, true);
+    assert_eq!(
+        <(i32, i32, i32, i32, i32, i32, i32, i32, i32, i32, i32, bool)>::from_json(
+            &twelve.to_json()
+        ),
+        Some(twelve)
+    );
+
+    let ranges: Vec<(f32, f32)> = vec![(0.0, 1.5), (2.0, 3.0)];
+    assert_eq!(
+        Vec::<(f32, f32)>::from_json(&ranges.to_json()),
+        Some(ranges)
+    );
+}
+
+#[test]
+fn tuple_length_mismatch_fails() {
+    assert_eq!(<(i32, i32)>::from_json("[1]"), None);
+    assert_eq!(<(i32, i32)>::from_json("[1, 2, 3]"), None);
+    assert_eq!(<(i32,)>::from_json("[1]"), Some((1,)));
+}
+
+#[test]
+fn unit() {
+    assert_eq!(().to_json(), "null");
+    assert_eq!(<()>::from_json("null"), Some(()));
+    assert_eq!(<((), i32)>::from_json("[null, 3]"), Some(((), 3)));
+    assert_eq!(<()>::from_json("1"), None);
+}